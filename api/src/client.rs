@@ -0,0 +1,207 @@
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::ClientError,
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use steel::*;
+
+use crate::{
+    consts::BUS_COUNT,
+    state::{bus_pda, config_pda, proof_pda, Bus, Config, Proof},
+};
+
+/// Errors returned when fetching program accounts.
+#[derive(Debug, Error)]
+pub enum CoalClientError {
+    #[error(transparent)]
+    Rpc(#[from] ClientError),
+    #[error("Account {0} does not exist")]
+    AccountNotFound(Pubkey),
+    #[error("Account {0} could not be deserialized")]
+    InvalidAccountData(Pubkey),
+}
+
+/// An async RPC client with typed getters for the program's accounts.
+pub struct CoalClient {
+    rpc: RpcClient,
+}
+
+impl CoalClient {
+    pub fn new(rpc: RpcClient) -> Self {
+        Self { rpc }
+    }
+
+    /// Returns the underlying RPC client.
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    /// Fetches the config of the mint.
+    pub async fn get_config(&self, mint: Pubkey) -> Result<Config, CoalClientError> {
+        self.get_state(config_pda(mint).0).await
+    }
+
+    /// Fetches the proof of the authority for the mint.
+    pub async fn get_proof(&self, mint: Pubkey, authority: Pubkey) -> Result<Proof, CoalClientError> {
+        self.get_state(proof_pda(mint, authority).0).await
+    }
+
+    /// Fetches the busses of the mint, ordered by id.
+    ///
+    /// Busses do not record their mint, so every bus is fetched and matched against the mint's
+    /// bus addresses.
+    pub async fn get_busses(&self, mint: Pubkey) -> Result<[Bus; BUS_COUNT], CoalClientError> {
+        let accounts = self.get_program_accounts::<Bus>().await?;
+        let mut busses = [Bus::zeroed(); BUS_COUNT];
+        for (id, bus) in busses.iter_mut().enumerate() {
+            let address = bus_pda(mint, id as u8).0;
+            *bus = accounts
+                .iter()
+                .find(|(pubkey, _)| pubkey.eq(&address))
+                .map(|(_, bus)| *bus)
+                .ok_or(CoalClientError::AccountNotFound(address))?;
+        }
+        Ok(busses)
+    }
+
+    /// Fetches every program account of the given type, filtered by its discriminator.
+    pub async fn get_program_accounts<T: AccountDeserialize + Discriminator + Pod>(
+        &self,
+    ) -> Result<Vec<(Pubkey, T)>, CoalClientError> {
+        let mut discriminator = [0; 8];
+        discriminator[0] = T::discriminator();
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(8 + std::mem::size_of::<T>() as u64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, discriminator.to_vec())),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        self.rpc
+            .get_program_accounts_with_config(&crate::ID, config)
+            .await?
+            .into_iter()
+            .map(|(pubkey, account)| Ok((pubkey, deserialize(pubkey, &account.data)?)))
+            .collect()
+    }
+
+    /// Fetches a program account of the given type.
+    pub async fn get_state<T: AccountDeserialize + Discriminator + Pod>(
+        &self,
+        address: Pubkey,
+    ) -> Result<T, CoalClientError> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(&address, self.rpc.commitment())
+            .await?
+            .value
+            .ok_or(CoalClientError::AccountNotFound(address))?;
+        deserialize(address, &account.data)
+    }
+}
+
+fn deserialize<T: AccountDeserialize + Discriminator + Pod>(
+    address: Pubkey,
+    data: &[u8],
+) -> Result<T, CoalClientError> {
+    T::try_from_bytes(data)
+        .copied()
+        .map_err(|_| CoalClientError::InvalidAccountData(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use solana_account_decoder::{UiAccount, UiAccountEncoding};
+    use solana_client::{
+        nonblocking::rpc_client::RpcClient,
+        rpc_request::RpcRequest,
+        rpc_response::{Response, RpcKeyedAccount, RpcResponseContext},
+    };
+    use solana_sdk::account::Account;
+    use steel::*;
+
+    use crate::{
+        client::{CoalClient, CoalClientError},
+        consts::{BUS_COUNT, MINT_ADDRESS},
+        state::{bus_pda, config_pda, Bus, Config},
+    };
+
+    fn ui_account<T: Discriminator + Pod>(address: Pubkey, state: T) -> UiAccount {
+        let mut data = vec![0; 8 + std::mem::size_of::<T>()];
+        data[0] = T::discriminator();
+        data[8..].copy_from_slice(bytemuck::bytes_of(&state));
+        let account = Account {
+            lamports: 1,
+            data,
+            owner: crate::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        UiAccount::encode(&address, &account, UiAccountEncoding::Base64, None, None)
+    }
+
+    #[tokio::test]
+    async fn test_get_config() {
+        let mut config = Config::zeroed();
+        config.mint = MINT_ADDRESS;
+        config.base_reward_rate = 64;
+        let address = config_pda(MINT_ADDRESS).0;
+        let response = Response {
+            context: RpcResponseContext { slot: 1, api_version: None },
+            value: Some(ui_account(address, config)),
+        };
+        let mocks = HashMap::from([(
+            RpcRequest::GetAccountInfo,
+            serde_json::to_value(response).unwrap(),
+        )]);
+        let client = CoalClient::new(RpcClient::new_mock_with_mocks("succeeds".into(), mocks));
+        assert_eq!(client.get_config(MINT_ADDRESS).await.unwrap(), config);
+    }
+
+    #[tokio::test]
+    async fn test_get_busses() {
+        let mut accounts = vec![];
+        for id in (0..BUS_COUNT).rev() {
+            let bus = Bus {
+                id: id as u64,
+                rewards: id as u64 * 10,
+                theoretical_rewards: 0,
+            };
+            for mint in [MINT_ADDRESS, Pubkey::new_unique()] {
+                let address = bus_pda(mint, id as u8).0;
+                accounts.push(RpcKeyedAccount {
+                    pubkey: address.to_string(),
+                    account: ui_account(address, bus),
+                });
+            }
+        }
+        let mocks = HashMap::from([(
+            RpcRequest::GetProgramAccounts,
+            serde_json::to_value(accounts).unwrap(),
+        )]);
+        let client = CoalClient::new(RpcClient::new_mock_with_mocks("succeeds".into(), mocks));
+        let busses = client.get_busses(MINT_ADDRESS).await.unwrap();
+        for (id, bus) in busses.iter().enumerate() {
+            assert_eq!(bus.id, id as u64);
+            assert_eq!(bus.rewards, id as u64 * 10);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_proof_not_found() {
+        let client = CoalClient::new(RpcClient::new_mock("succeeds".into()));
+        let authority = Pubkey::new_unique();
+        assert!(matches!(
+            client.get_proof(MINT_ADDRESS, authority).await,
+            Err(CoalClientError::AccountNotFound(_))
+        ));
+    }
+}
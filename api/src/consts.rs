@@ -4,8 +4,14 @@ use solana_program::{pubkey, pubkey::Pubkey};
 use ore_api;
 
 /// The authority allowed to initialize the program.
+#[cfg(not(feature = "localnet"))]
 pub const INITIALIZER_ADDRESS: Pubkey = pubkey!("FJka1yJHn1SWux2X1o8VqHC8uaAWGv6CbNQvPWLJQufq");
 
+/// The authority allowed to initialize the program on a local validator, whose keypair is derived
+/// from the seed `[7; 32]`.
+#[cfg(feature = "localnet")]
+pub const INITIALIZER_ADDRESS: Pubkey = pubkey!("GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB");
+
 /// The base reward rate to intialize the program with.
 pub const INITIAL_BASE_REWARD_RATE: u64 = BASE_REWARD_RATE_MIN_THRESHOLD;
 
@@ -62,6 +68,31 @@ static_assertions::const_assert!(
     (MAX_EPOCH_REWARDS / BUS_COUNT as u64) * BUS_COUNT as u64 == MAX_EPOCH_REWARDS
 );
 
+/// Mine policy flag allowing at most one COAL mine, pool mine, or pool submission per transaction.
+pub const MINE_POLICY_SINGLE_MINE: u64 = 1 << 0;
+
+/// Mine policy flag requiring an earlier auth instruction carrying the proof address.
+pub const MINE_POLICY_REQUIRE_AUTH: u64 = 1 << 1;
+
+/// Mine policy flag rejecting mines by CPI, unless the invoking program is on the allowlist.
+pub const MINE_POLICY_CPI_ALLOWLIST: u64 = 1 << 2;
+
+/// The number of programs that can be allowed to mine by CPI.
+pub const MINE_CPI_ALLOWLIST_SIZE: usize = 4;
+
+/// The maximum number of member solutions in one pool submission.
+pub const MAX_POOL_SUBMISSIONS: usize = 8;
+
+/// The number of nonces reserved for each pool member. A pool submission only counts a solution
+/// for a member if its nonce is in the member's range, so members cannot pass off each other's work.
+pub const POOL_MEMBER_NONCES: u64 = 1 << 32;
+
+/// The maximum number of COAL boosts applied to one hash.
+pub const MAX_COAL_BOOSTS: usize = 3;
+
+/// The seed of boost account PDAs.
+pub const BOOST: &[u8] = b"boost";
+
 /// The seed of the bus account PDA.
 pub const BUS: &[u8] = b"bus";
 
@@ -74,9 +105,18 @@ pub const METADATA: &[u8] = b"metadata";
 /// The seed of the mint account PDA.
 pub const MINT: &[u8] = b"mint";
 
+/// The seed of pool account PDAs.
+pub const POOL: &[u8] = b"pool";
+
+/// The seed of pool member account PDAs.
+pub const POOL_MEMBER: &[u8] = b"pool_member";
+
 /// The seed of proof account PDAs.
 pub const PROOF: &[u8] = b"proof";
 
+/// The seed of stake account PDAs.
+pub const STAKE: &[u8] = b"stake";
+
 /// The seed of the treasury account PDA.
 pub const TREASURY: &[u8] = b"treasury";
 
@@ -166,3 +206,36 @@ pub const TREASURY_TOKENS_ADDRESS: Pubkey = Pubkey::new_from_array(
 
 /// The address of the CU-optimized Solana noop program.
 pub const NOOP_PROGRAM_ID: Pubkey = pubkey!("noop8ytexvkpCuqbf6FB89BSuNemHtPRqaNC31GWivW");
+
+/// The address of the Solana compute budget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+// Compute units budgeted per instruction. These are estimates that have not been measured on an
+// SBF build yet. The bench in program/tests/compute_units.rs fails when an instruction exceeds its
+// value by more than the regression threshold, and records measured values with
+// `BENCH_UPDATE=1 cargo test-sbf --features localnet --test compute_units -- --include-ignored`.
+pub const RESET_COMPUTE_UNITS: u32 = 40_000;
+pub const CLAIM_COMPUTE_UNITS: u32 = 20_000;
+pub const UPDATE_COMPUTE_UNITS: u32 = 5_000;
+pub const OPEN_STAKE_COMPUTE_UNITS: u32 = 15_000;
+pub const DEPOSIT_COMPUTE_UNITS: u32 = 15_000;
+pub const WITHDRAW_COMPUTE_UNITS: u32 = 15_000;
+pub const JOIN_POOL_COMPUTE_UNITS: u32 = 15_000;
+pub const CLAIM_POOL_COMPUTE_UNITS: u32 = 20_000;
+pub const OPEN_COMPUTE_UNITS: u32 = 30_000;
+pub const MINE_COMPUTE_UNITS: u32 = 50_000;
+pub const COAL_BOOST_COMPUTE_UNITS: u32 = 10_000;
+
+// Compute units budgeted for the work of the ORE and ORE boost programs this program invokes.
+// These are not benched, since the tests run unmetered native stand-ins for those programs.
+pub const ORE_MINE_COMPUTE_UNITS: u32 = 450_000;
+pub const ORE_BOOST_COMPUTE_UNITS: u32 = 20_000;
+pub const ORE_CLAIM_COMPUTE_UNITS: u32 = 30_000;
+
+/// Compute units budgeted for the compute budget and auth instructions of a transaction.
+pub const TX_OVERHEAD_COMPUTE_UNITS: u32 = 2_000;
+
+/// Headroom added to the budgeted compute units when sizing a compute unit limit, in basis points.
+/// It must stay above the bench's regression threshold.
+pub const COMPUTE_UNIT_HEADROOM_BPS: u32 = 1_000;
@@ -0,0 +1,156 @@
+use steel::*;
+
+/// Creates an associated token account under the provided token program.
+#[inline(always)]
+pub fn create_associated_token_account_interface<'info>(
+    funder_info: &AccountInfo<'info>,
+    owner_info: &AccountInfo<'info>,
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_associated_token_account::instruction::create_associated_token_account(
+            funder_info.key,
+            owner_info.key,
+            mint_info.key,
+            token_program.key,
+        ),
+        &[
+            funder_info.clone(),
+            token_account_info.clone(),
+            owner_info.clone(),
+            mint_info.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )
+}
+
+/// Transfers tokens from a signer's token account under the provided token program.
+#[inline(always)]
+pub fn transfer_checked_interface<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            from_info.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[authority_info.key],
+            amount,
+            decimals,
+        )?,
+        &[
+            token_program.clone(),
+            from_info.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
+
+/// Transfers tokens from a PDA-owned token account under the provided token program.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn transfer_checked_signed<'info>(
+    authority_info: &AccountInfo<'info>,
+    from_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            from_info.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[authority_info.key],
+            amount,
+            decimals,
+        )?,
+        &[
+            token_program.clone(),
+            from_info.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+        authority_info.owner,
+        seeds,
+    )
+}
+
+/// Mints tokens with a PDA mint authority under the provided token program.
+#[inline(always)]
+pub fn mint_to_interface_signed<'info>(
+    mint_info: &AccountInfo<'info>,
+    to_info: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_program.key,
+            mint_info.key,
+            to_info.key,
+            authority_info.key,
+            &[authority_info.key],
+            amount,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            to_info.clone(),
+            authority_info.clone(),
+        ],
+        authority_info.owner,
+        seeds,
+    )
+}
+
+/// Burns tokens from a signer's token account under the provided token program.
+#[inline(always)]
+pub fn burn_interface<'info>(
+    token_account_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> ProgramResult {
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::burn(
+            token_program.key,
+            token_account_info.key,
+            mint_info.key,
+            authority_info.key,
+            &[authority_info.key],
+            amount,
+        )?,
+        &[
+            token_program.clone(),
+            token_account_info.clone(),
+            mint_info.clone(),
+            authority_info.clone(),
+        ],
+    )
+}
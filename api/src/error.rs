@@ -19,6 +19,48 @@ pub enum OreError {
     MaxSupply = 6,
     #[error("The proof does not match the expected account")]
     AuthFailed = 7,
+    #[error("The redemption amount is too small to receive any ORE")]
+    RedeemTooSmall = 8,
+    #[error("Migration is not open for this mint")]
+    MigrationClosed = 9,
+    #[error("The epoch has not ended yet")]
+    ResetTooEarly = 10,
+    #[error("The mine accounts do not match the counts in the args")]
+    InvalidMineAccounts = 11,
+    #[error("An arithmetic operation overflowed")]
+    ArithmeticOverflow = 12,
+    #[error("The mint does not match the config")]
+    InvalidMint = 13,
+    #[error("The bus does not belong to the config's mint")]
+    InvalidBus = 14,
+    #[error("The proof does not belong to the config's mint")]
+    InvalidProof = 15,
+    #[error("The ORE accounts are not the ones derived from the proof")]
+    InvalidOreAccounts = 16,
+    #[error("Only one mine is allowed per transaction, and per proof in each slot")]
+    TooManyMines = 17,
+    #[error("The mine is not preceded by an auth instruction for the proof")]
+    MissingAuth = 18,
+    #[error("The program invoking mine is not on the allowlist")]
+    CpiNotAllowed = 19,
+    #[error("The pool does not mine with this proof")]
+    InvalidPool = 20,
+    #[error("The member does not belong to this pool")]
+    InvalidPoolMember = 21,
+    #[error("A pool submission needs one solution per distinct member, up to the maximum")]
+    InvalidPoolSubmission = 22,
+    #[error("The stake does not belong to this boost and proof")]
+    InvalidBoost = 23,
+    #[error("The withdrawal amount cannot be greater than the stake")]
+    WithdrawTooLarge = 24,
+    #[error("This instruction has been retired or is not enabled in this build")]
+    Deprecated = 25,
+    #[error("The account was created under an earlier layout and needs to be migrated")]
+    NeedsMigration = 26,
+    #[error("The upgrade amount is too small to receive any tokens")]
+    UpgradeTooSmall = 27,
+    #[error("A pool solution's nonce is not in the range of its member")]
+    InvalidPoolNonce = 28,
 }
 
 error!(OreError);
@@ -9,3 +9,21 @@ pub struct MineEvent {
 }
 
 event!(MineEvent);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct RedeemEvent {
+    pub amount: u64,
+    pub ore_amount: u64,
+}
+
+event!(RedeemEvent);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct BurnEvent {
+    pub amount: u64,
+    pub total_burned: u64,
+}
+
+event!(BurnEvent);
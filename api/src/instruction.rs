@@ -1,6 +1,6 @@
 use steel::*;
 
-use crate::state::Ingredient;
+use crate::consts::{MAX_POOL_SUBMISSIONS, MINE_CPI_ALLOWLIST_SIZE};
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, TryFromPrimitive)]
@@ -14,11 +14,24 @@ pub enum OreInstruction {
     #[deprecated(since = "2.4.0", note = "Please stake with the boost program")]
     Stake = 5,
     Update = 6,
-    #[deprecated(since = "2.6.0", note = "v1 tokens are no longer eligable to upgrade")]
     Upgrade = 7,
+    Redeem = 8,
+    Burn = 9,
+    OpenPool = 10,
+    JoinPool = 11,
+    MinePool = 12,
+    SubmitPool = 13,
+    ClaimPool = 14,
+    OpenStake = 15,
+    Deposit = 16,
+    Withdraw = 17,
+    Migrate = 18,
 
     // Admin
     Initialize = 100,
+    SetMigration = 101,
+    SetMinePolicy = 102,
+    NewBoost = 103,
 }
 
 #[repr(C)]
@@ -36,6 +49,10 @@ pub struct Close {}
 pub struct Mine {
     pub digest: [u8; 16],
     pub nonce: [u8; 8],
+    /// Number of COAL boost accounts at the end of the account list.
+    pub coal_boost_accounts: u8,
+    /// Number of ORE boost accounts after the required accounts, either 0 or 3.
+    pub ore_boost_accounts: u8,
 }
 
 #[repr(C)]
@@ -46,40 +63,150 @@ pub struct Open {
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct Reset {}
+pub struct Reset {
+    /// Fails with `ResetTooEarly` instead of succeeding as a no-op if the epoch has not ended.
+    pub strict: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Update {}
 
-#[deprecated(since = "2.4.0", note = "Please stake with the boost program")]
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct Stake {
+pub struct Upgrade {
     pub amount: [u8; 8],
 }
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct Update {}
+pub struct Redeem {
+    pub amount: [u8; 8],
+}
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct Upgrade {
+pub struct Burn {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OpenPool {
+    pub proof_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct JoinPool {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MinePool {
+    pub digest: [u8; 16],
+    pub nonce: [u8; 8],
+    /// Number of COAL boost accounts at the end of the account list.
+    pub coal_boost_accounts: u8,
+    /// Number of ORE boost accounts after the required accounts, either 0 or 3.
+    pub ore_boost_accounts: u8,
+}
+
+impl MinePool {
+    /// The args of the mine this pool mine submits.
+    pub fn mine(&self) -> Mine {
+        Mine {
+            digest: self.digest,
+            nonce: self.nonce,
+            coal_boost_accounts: self.coal_boost_accounts,
+            ore_boost_accounts: self.ore_boost_accounts,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SubmitPool {
+    /// Number of COAL boost accounts before the member accounts.
+    pub coal_boost_accounts: u8,
+    /// Number of ORE boost accounts after the required accounts, either 0 or 3.
+    pub ore_boost_accounts: u8,
+    /// Number of member accounts at the end of the account list, one per solution.
+    pub members: u8,
+    /// The best solution of each member on the pool's ORE proof challenge, as digest and nonce.
+    /// Each nonce must be in its member's nonce range.
+    pub solutions: [[u8; 24]; MAX_POOL_SUBMISSIONS],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ClaimPool {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OpenStake {}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Deposit {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Withdraw {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Migrate {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Initialize {
-    pub treasury_bump: u64,
-    pub mint_bump: u64,
+    pub treasury_bump: u8,
+    pub mint_bump: u8,
     pub mint_noise: [u8; 16],
     pub metadata_name: [u8; 32],
     pub metadata_symbol: [u8; 8],
     pub metadata_uri: [u8; 128],
     pub migration_mint: Pubkey,
-    pub max_supply: u64,
-    pub schedule_epochs: u64,
-    pub decay_basis_points: u64,
-    pub wrapped_ingredient: Ingredient,
-    pub burned_ingredient: Ingredient,
+    pub max_supply: [u8; 8],
+    pub schedule_epochs: [u8; 8],
+    pub decay_basis_points: [u8; 8],
+    pub wrapped_ingredient_mint: Pubkey,
+    pub wrapped_ingredient_ratio: [u8; 8],
+    pub burned_ingredient_mint: Pubkey,
+    pub burned_ingredient_ratio: [u8; 8],
+    pub recycle_burns: [u8; 8],
+    pub migration_ratio: [u8; 8],
+    pub migration_deadline: [u8; 8],
+    pub crank_reward: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMigration {
+    pub migration_mint: Pubkey,
+    pub migration_ratio: [u8; 8],
+    pub migration_deadline: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetMinePolicy {
+    pub mine_policy: [u8; 8],
+    pub mine_cpi_allowlist: [Pubkey; MINE_CPI_ALLOWLIST_SIZE],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct NewBoost {
+    /// The bonus paid to a staker holding all of the stake, in basis points of the mining reward.
+    pub multiplier: [u8; 8],
+    pub expires_at: [u8; 8],
 }
 
 instruction!(OreInstruction, Claim);
@@ -87,7 +214,20 @@ instruction!(OreInstruction, Close);
 instruction!(OreInstruction, Mine);
 instruction!(OreInstruction, Open);
 instruction!(OreInstruction, Reset);
-instruction!(OreInstruction, Stake);
 instruction!(OreInstruction, Update);
 instruction!(OreInstruction, Upgrade);
+instruction!(OreInstruction, Redeem);
+instruction!(OreInstruction, Burn);
+instruction!(OreInstruction, OpenPool);
+instruction!(OreInstruction, JoinPool);
+instruction!(OreInstruction, MinePool);
+instruction!(OreInstruction, SubmitPool);
+instruction!(OreInstruction, ClaimPool);
+instruction!(OreInstruction, OpenStake);
+instruction!(OreInstruction, Deposit);
+instruction!(OreInstruction, Withdraw);
+instruction!(OreInstruction, Migrate);
 instruction!(OreInstruction, Initialize);
+instruction!(OreInstruction, SetMigration);
+instruction!(OreInstruction, SetMinePolicy);
+instruction!(OreInstruction, NewBoost);
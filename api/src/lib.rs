@@ -1,22 +1,35 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod consts;
+pub mod cpi;
 pub mod error;
 pub mod event;
 #[allow(deprecated)]
 pub mod instruction;
 pub mod loaders;
+pub mod logic;
 pub mod sdk;
 pub mod state;
+pub mod tx;
 
 pub mod prelude {
     pub use crate::consts::*;
+    pub use crate::cpi::*;
     pub use crate::error::*;
     pub use crate::event::*;
     pub use crate::instruction::*;
     pub use crate::loaders::*;
+    pub use crate::logic::*;
     pub use crate::sdk::*;
     pub use crate::state::*;
+    pub use crate::tx::*;
 }
 
 use steel::*;
 
+#[cfg(not(feature = "localnet"))]
 declare_id!("oreV2ZymfyeXgNgBdqMkumTqqAprVqgBWQfoYkrtKWQ");
+
+// Deploys at its own id on a local validator, so the ORE program can be loaded alongside it.
+#[cfg(feature = "localnet")]
+declare_id!("GpAeEqYCScaRgrzsPqandEs98zfxse4XeoioLVHo1Gtg");
@@ -1,7 +1,10 @@
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use spl_token_2022::extension::StateWithExtensions;
 use steel::*;
 
 use crate::{
     consts::*,
+    error::OreError,
     state::{Config, Treasury},
 };
 
@@ -10,6 +13,8 @@ pub trait OreAccountInfoValidation {
     fn is_config(&self) -> Result<&Self, ProgramError>;
     fn is_treasury(&self) -> Result<&Self, ProgramError>;
     fn is_treasury_tokens(&self) -> Result<&Self, ProgramError>;
+    fn has_current_layout<T: Pod>(&self) -> Result<&Self, ProgramError>;
+    fn has_address_err(&self, address: &Pubkey, err: OreError) -> Result<&Self, ProgramError>;
 }
 
 impl OreAccountInfoValidation for AccountInfo<'_> {
@@ -33,4 +38,99 @@ impl OreAccountInfoValidation for AccountInfo<'_> {
     fn is_treasury_tokens(&self) -> Result<&Self, ProgramError> {
         self.has_address(&TREASURY_TOKENS_ADDRESS)
     }
+
+    /// Fails with `NeedsMigration` unless the account is sized for the current layout of `T`.
+    /// The steel loaders do not check the size, so they would read past the end of an account
+    /// created under an earlier layout.
+    fn has_current_layout<T: Pod>(&self) -> Result<&Self, ProgramError> {
+        if self.data_len().ne(&(8 + std::mem::size_of::<T>())) {
+            return Err(OreError::NeedsMigration.into());
+        }
+        Ok(self)
+    }
+
+    /// Like `has_address`, but fails with the given error so clients can tell which account
+    /// is wrong.
+    fn has_address_err(&self, address: &Pubkey, err: OreError) -> Result<&Self, ProgramError> {
+        if self.key.ne(address) {
+            return Err(err.into());
+        }
+        Ok(self)
+    }
+}
+
+/// Loaders for mints and token accounts owned by either the SPL Token or Token-2022 program.
+///
+/// Token-2022 state is unpacked with its extensions and returned as the classic SPL types, so
+/// processors can validate either program's accounts with the same assertions.
+pub trait AsTokenInterface {
+    fn is_token_program(&self) -> Result<&Self, ProgramError>;
+    fn as_mint_interface(&self) -> Result<Mint, ProgramError>;
+    fn as_token_account_interface(&self) -> Result<TokenAccount, ProgramError>;
+    fn as_associated_token_account_interface(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<TokenAccount, ProgramError>;
+}
+
+impl AsTokenInterface for AccountInfo<'_> {
+    fn is_token_program(&self) -> Result<&Self, ProgramError> {
+        if self.key.ne(&spl_token::ID) && self.key.ne(&spl_token_2022::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        self.is_executable()
+    }
+
+    fn as_mint_interface(&self) -> Result<Mint, ProgramError> {
+        if self.owner.eq(&spl_token::ID) {
+            return self.as_mint();
+        }
+        self.has_owner(&spl_token_2022::ID)?;
+        let data = self.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?.base;
+        Ok(Mint {
+            mint_authority: mint.mint_authority,
+            supply: mint.supply,
+            decimals: mint.decimals,
+            is_initialized: mint.is_initialized,
+            freeze_authority: mint.freeze_authority,
+        })
+    }
+
+    fn as_token_account_interface(&self) -> Result<TokenAccount, ProgramError> {
+        if self.owner.eq(&spl_token::ID) {
+            return self.as_token_account();
+        }
+        self.has_owner(&spl_token_2022::ID)?;
+        let data = self.try_borrow_data()?;
+        let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?.base;
+        Ok(TokenAccount {
+            mint: account.mint,
+            owner: account.owner,
+            amount: account.amount,
+            delegate: account.delegate,
+            state: match account.state {
+                spl_token_2022::state::AccountState::Uninitialized => AccountState::Uninitialized,
+                spl_token_2022::state::AccountState::Initialized => AccountState::Initialized,
+                spl_token_2022::state::AccountState::Frozen => AccountState::Frozen,
+            },
+            is_native: account.is_native,
+            delegated_amount: account.delegated_amount,
+            close_authority: account.close_authority,
+        })
+    }
+
+    fn as_associated_token_account_interface(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<TokenAccount, ProgramError> {
+        self.has_address(
+            &spl_associated_token_account::get_associated_token_address_with_program_id(
+                owner, mint, self.owner,
+            ),
+        )?
+        .as_token_account_interface()
+    }
 }
@@ -0,0 +1,577 @@
+use crate::{consts::*, error::OreError, state::{Boost, Bus, Config, Stake}};
+
+/// Rewards earned by a single hash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MineReward {
+    /// The reward ignoring the bus limit, tracked so the base reward rate follows the real
+    /// hashpower on the network.
+    pub theoretical: u64,
+
+    /// The reward actually paid out to the miner.
+    pub actual: u64,
+}
+
+/// Calculates the reward for a hash of the given difficulty submitted to the bus. Fails with
+/// `HashTooEasy` if the difficulty is below the min difficulty.
+pub fn compute_mine_reward(
+    config: &Config,
+    bus: &Bus,
+    difficulty: u32,
+) -> Result<MineReward, OreError> {
+    // Normalize the difficulty and calculate the reward amount.
+    //
+    // The reward doubles for every bit of difficulty (leading zeros) on the hash. We use the normalized
+    // difficulty so the minimum accepted difficulty pays out at the base reward rate. Rewards too large
+    // for a u64 saturate, and are capped by the bus limit below.
+    let normalized_difficulty = (difficulty as u64)
+        .checked_sub(config.min_difficulty)
+        .ok_or(OreError::HashTooEasy)?;
+    let reward = u32::try_from(normalized_difficulty)
+        .ok()
+        .and_then(|d| 2u64.checked_pow(d))
+        .map_or(u64::MAX, |multiplier| {
+            config.base_reward_rate.saturating_mul(multiplier)
+        });
+
+    // Apply bus limit.
+    //
+    // Busses are limited to distributing 1 ORE per epoch. The payout amount must be capped to whatever is
+    // left in the selected bus. This limits the maximum amount that will be paid out for any given hash to 1 ORE.
+    let reward_actual = reward.min(bus.rewards).min(config.get_epoch_rewards().saturating_div(BUS_COUNT as u64));
+
+    Ok(MineReward {
+        theoretical: reward,
+        actual: reward_actual,
+    })
+}
+
+/// Amounts minted by a reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResetMint {
+    /// The rewards paid out last epoch, minted to the treasury.
+    pub treasury: u64,
+
+    /// The incentive paid to the caller of the reset, minted after the treasury funding.
+    pub crank: u64,
+}
+
+/// Advances the config to the next epoch, refills the busses, and retargets the base reward rate
+/// and min difficulty. Returns the amounts to mint for the new epoch, given the current supply of
+/// the mint.
+pub fn apply_reset(
+    config: &mut Config,
+    busses: &mut [Bus],
+    supply: u64,
+) -> Result<ResetMint, OreError> {
+    config.current_epoch = config.current_epoch.saturating_add(1);
+
+    // Reset bus accounts and calculate actual rewards mined since last reset.
+    let target_epoch_rewards = config.get_epoch_rewards();
+    let mut total_remaining_rewards = 0u64;
+    let mut total_theoretical_rewards = 0u64;
+
+    for bus in busses.iter_mut() {
+        // Track accumulators.
+        total_remaining_rewards = total_remaining_rewards.saturating_add(bus.rewards);
+        total_theoretical_rewards =
+            total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
+
+        // Reset bus account for new epoch.
+        bus.rewards = config.get_epoch_rewards();
+        bus.theoretical_rewards = 0;
+    }
+    let max_epoch_rewards = target_epoch_rewards.saturating_mul(BUS_COUNT as u64);
+    let total_epoch_rewards = max_epoch_rewards.saturating_sub(total_remaining_rewards);
+
+    // Update base reward rate for next epoch.
+    config.base_reward_rate =
+        calculate_new_reward_rate(config.base_reward_rate, total_theoretical_rewards);
+
+    // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
+    let base_reward_rate_min_threshold = BASE_REWARD_RATE_MIN_THRESHOLD.saturating_mul(target_epoch_rewards).saturating_div(ONE_ORE);
+    
+    if config.base_reward_rate.le(&base_reward_rate_min_threshold) {
+        config.min_difficulty = config
+            .min_difficulty
+            .checked_add(1)
+            .ok_or(OreError::ArithmeticOverflow)?;
+        config.base_reward_rate = config
+            .base_reward_rate
+            .checked_mul(2)
+            .ok_or(OreError::ArithmeticOverflow)?;
+    }
+
+    // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
+    let base_reward_rate_max_threshold = BASE_REWARD_RATE_MAX_THRESHOLD.saturating_mul(target_epoch_rewards).saturating_div(ONE_ORE);
+    
+    if config.base_reward_rate.ge(&base_reward_rate_max_threshold) && config.min_difficulty.gt(&1) {
+        config.min_difficulty = config.min_difficulty.saturating_sub(1);
+        config.base_reward_rate = config.base_reward_rate.saturating_div(2);
+    }
+
+    // Halving can zero out a rate of 1 once the epoch rewards have decayed to nothing.
+    config.base_reward_rate = config.base_reward_rate.clamp(1, BUS_EPOCH_REWARDS);
+
+    // Max supply check.
+    let emitted_supply = config.emitted_supply(supply);
+    if emitted_supply.ge(&config.max_supply) {
+        return Err(OreError::MaxSupply);
+    }
+
+    // Mint the rewards paid out last epoch, then the crank reward, up to the max supply.
+    let remaining_supply = config.max_supply.saturating_sub(emitted_supply);
+    let treasury = remaining_supply.min(total_epoch_rewards);
+    let crank = remaining_supply.saturating_sub(treasury).min(config.crank_reward);
+
+    Ok(ResetMint { treasury, crank })
+}
+
+/// This function calculates what the new reward rate should be based on how many total rewards
+/// were mined in the prior epoch. The math is largely identitical to function used by the Bitcoin
+/// network to update the difficulty between each epoch.
+///
+/// new_rate = current_rate * (target_rewards / actual_rewards)
+///
+/// The new rate is then smoothed by a constant factor to avoid large fluctuations. In Ore's case,
+/// the epochs are short (60 seconds) so a smoothing factor of 2 has been chosen. That is, the reward rate
+/// can at most double or halve from one epoch to the next.
+pub fn calculate_new_reward_rate(current_rate: u64, epoch_rewards: u64) -> u64 {
+    // Avoid division by zero. Leave the reward rate unchanged, if detected.
+    if epoch_rewards.eq(&0) {
+        return current_rate;
+    }
+
+    // Calculate new reward rate.
+    let new_rate = (current_rate as u128)
+        .saturating_mul(TARGET_EPOCH_REWARDS as u128)
+        .saturating_div(epoch_rewards as u128) as u64;
+
+    // Smooth reward rate so it cannot change by more than a constant factor from one epoch to the next.
+    let new_rate_min = current_rate.saturating_div(SMOOTHING_FACTOR);
+    let new_rate_max = current_rate.saturating_mul(SMOOTHING_FACTOR);
+    let new_rate_smoothed = new_rate.min(new_rate_max).max(new_rate_min);
+
+    // Prevent reward rate from dropping below 1 or exceeding BUS_EPOCH_REWARDS and return.
+    new_rate_smoothed.clamp(1, BUS_EPOCH_REWARDS)
+}
+
+/// Calculates the bonus a boost pays on top of a mining reward, proportional to the staker's
+/// share of the boost's total stake.
+pub fn compute_boost_reward(reward: u64, multiplier: u64, stake: u64, total_stake: u64) -> u64 {
+    (reward as u128)
+        .saturating_mul(multiplier as u128)
+        .saturating_mul(stake.min(total_stake) as u128)
+        .checked_div((total_stake as u128).saturating_mul(10_000))
+        .map_or(0, |bonus| bonus.min(u64::MAX as u128) as u64)
+}
+
+/// Adds the bonus of a boost to a mining reward if the boost is active and the stake has settled.
+/// The bonus is paid on the base reward, and the total stays capped by the bus's remaining rewards.
+pub fn apply_boost(
+    reward: u64,
+    base_reward: u64,
+    boost: &Boost,
+    stake: &Stake,
+    now: i64,
+    bus_rewards: u64,
+) -> u64 {
+    if !boost.is_active(now) || !stake.is_settled(now) {
+        return reward;
+    }
+    let bonus = compute_boost_reward(base_reward, boost.multiplier, stake.balance, boost.total_stake);
+    reward.saturating_add(bonus).min(bus_rewards)
+}
+
+/// Splits a pool's reward between its members proportionally to the difficulty each member
+/// submitted. Shares round down, so the remainder stays in the pool's proof.
+pub fn split_pool_reward(reward: u64, difficulties: &[u64]) -> Vec<u64> {
+    let total_difficulty: u128 = difficulties.iter().map(|d| *d as u128).sum();
+    difficulties
+        .iter()
+        .map(|difficulty| {
+            (reward as u128)
+                .saturating_mul(*difficulty as u128)
+                .checked_div(total_difficulty)
+                .unwrap_or(0) as u64
+        })
+        .collect()
+}
+
+/// Converts an amount of the migration token into the v2 token, adjusting for the difference in
+/// decimals before applying the migration ratio (in basis points).
+///
+/// upgrade_amount = amount * 10^(to_decimals - from_decimals) * ratio / 10000
+pub fn calculate_upgrade_amount(
+    amount: u64,
+    from_decimals: u8,
+    to_decimals: u8,
+    ratio: u64,
+) -> u64 {
+    let scale = 10u128
+        .checked_pow(to_decimals.abs_diff(from_decimals) as u32)
+        .unwrap_or(u128::MAX);
+    let upgrade_amount = if to_decimals.ge(&from_decimals) {
+        (amount as u128).saturating_mul(scale)
+    } else {
+        (amount as u128).saturating_div(scale)
+    };
+    upgrade_amount
+        .saturating_mul(ratio as u128)
+        .saturating_div(10_000)
+        .min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+    use rand::{distributions::Uniform, Rng};
+
+    use steel::Zeroable;
+
+    use crate::{
+        consts::{
+            BASE_REWARD_RATE_MIN_THRESHOLD, BUS_COUNT, BUS_EPOCH_REWARDS, INITIAL_BASE_REWARD_RATE,
+            INITIAL_MIN_DIFFICULTY, MAX_EPOCH_REWARDS, MAX_POOL_SUBMISSIONS, ONE_ORE,
+            SMOOTHING_FACTOR,
+            TARGET_EPOCH_REWARDS, TOKEN_DECIMALS, TOKEN_DECIMALS_V1,
+        },
+        error::OreError,
+        logic::{
+            apply_reset, calculate_new_reward_rate, calculate_upgrade_amount, compute_boost_reward,
+            compute_mine_reward, split_pool_reward, ResetMint,
+        },
+        state::{Bus, Config},
+    };
+
+    const FUZZ_SIZE: u64 = 10_000;
+
+    #[test]
+    fn test_calculate_new_reward_rate_target() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS);
+        assert!(new_rate.eq(&current_rate));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_div_by_zero() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(current_rate, 0);
+        assert!(new_rate.eq(&current_rate));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_lower() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS.saturating_add(1_000_000_000),
+        );
+        assert!(new_rate.lt(&current_rate));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_lower_edge() {
+        let current_rate = BASE_REWARD_RATE_MIN_THRESHOLD;
+        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS + 1);
+        assert!(new_rate.lt(&current_rate));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_lower_fuzz() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..FUZZ_SIZE {
+            let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
+            let actual_rewards: u64 =
+                rng.sample(Uniform::new(TARGET_EPOCH_REWARDS, MAX_EPOCH_REWARDS));
+            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
+            assert!(new_rate.lt(&current_rate));
+        }
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_higher() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_EPOCH_REWARDS.saturating_sub(1_000_000_000),
+        );
+        assert!(new_rate.gt(&current_rate));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_higher_fuzz() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..FUZZ_SIZE {
+            let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
+            let actual_rewards: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
+            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
+            assert!(new_rate.gt(&current_rate));
+        }
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_max_smooth() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(current_rate, 1);
+        assert!(new_rate.eq(&current_rate.saturating_mul(SMOOTHING_FACTOR)));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_min_smooth() {
+        let current_rate = 1000;
+        let new_rate = calculate_new_reward_rate(current_rate, u64::MAX);
+        assert!(new_rate.eq(&current_rate.saturating_div(SMOOTHING_FACTOR)));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_max_inputs() {
+        let new_rate = calculate_new_reward_rate(BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS);
+        assert!(new_rate.eq(&BUS_EPOCH_REWARDS.saturating_div(SMOOTHING_FACTOR)));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_min_inputs() {
+        let new_rate = calculate_new_reward_rate(1, 1);
+        assert!(new_rate.eq(&1u64.saturating_mul(SMOOTHING_FACTOR)));
+    }
+
+    fn test_config() -> Config {
+        let mut config = Config::zeroed();
+        config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+        config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+        config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+        config
+    }
+
+    #[test]
+    fn test_compute_mine_reward_doubles_per_difficulty() {
+        let config = test_config();
+        let mut bus = Bus::zeroed();
+        bus.rewards = BUS_EPOCH_REWARDS;
+        let base = compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY).unwrap();
+        assert_eq!(base.theoretical, INITIAL_BASE_REWARD_RATE);
+        assert_eq!(base.actual, INITIAL_BASE_REWARD_RATE);
+        let reward = compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY + 3).unwrap();
+        assert_eq!(reward.theoretical, INITIAL_BASE_REWARD_RATE * 8);
+        assert_eq!(reward.actual, INITIAL_BASE_REWARD_RATE * 8);
+    }
+
+    #[test]
+    fn test_compute_mine_reward_bus_cap() {
+        let config = test_config();
+        let mut bus = Bus::zeroed();
+        bus.rewards = 100;
+        let reward = compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY + 10).unwrap();
+        assert_eq!(reward.theoretical, INITIAL_BASE_REWARD_RATE * 1024);
+        assert_eq!(reward.actual, 100);
+
+        // A full bus still pays out at most its share of the epoch rewards.
+        bus.rewards = u64::MAX;
+        let reward = compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY + 40).unwrap();
+        assert_eq!(reward.actual, TARGET_EPOCH_REWARDS / BUS_COUNT as u64);
+    }
+
+    #[test]
+    fn test_compute_mine_reward_hash_too_easy() {
+        let config = test_config();
+        let bus = Bus::zeroed();
+        assert_eq!(
+            compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY - 1),
+            Err(OreError::HashTooEasy)
+        );
+        assert_eq!(compute_mine_reward(&config, &bus, 0), Err(OreError::HashTooEasy));
+    }
+
+    #[test]
+    fn test_compute_mine_reward_saturates() {
+        let config = test_config();
+        let mut bus = Bus::zeroed();
+        bus.rewards = BUS_EPOCH_REWARDS;
+        let bus_cap = TARGET_EPOCH_REWARDS / BUS_COUNT as u64;
+        for difficulty in [60, 63, 64, 65, 128, u32::MAX] {
+            let reward =
+                compute_mine_reward(&config, &bus, INITIAL_MIN_DIFFICULTY.saturating_add(difficulty))
+                    .unwrap();
+            assert_eq!(reward.theoretical, u64::MAX);
+            assert_eq!(reward.actual, bus_cap);
+        }
+    }
+
+    #[test]
+    fn test_apply_reset_crank_reward() {
+        let mut config = Config::zeroed();
+        config.max_supply = ONE_ORE * 1_000;
+        config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+        config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+        config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+        config.crank_reward = ONE_ORE / 100;
+        let mut busses = [Bus::zeroed(); BUS_COUNT];
+        let mint = apply_reset(&mut config, &mut busses, 0).unwrap();
+        assert_eq!(
+            mint,
+            ResetMint {
+                treasury: TARGET_EPOCH_REWARDS * BUS_COUNT as u64,
+                crank: ONE_ORE / 100,
+            }
+        );
+
+        // Near the max supply, the treasury is funded first and the crank gets what is left.
+        let treasury = mint.treasury;
+        let supply = config.max_supply - treasury - ONE_ORE / 1_000;
+        let mut busses = [Bus::zeroed(); BUS_COUNT];
+        let mint = apply_reset(&mut config, &mut busses, supply).unwrap();
+        assert_eq!(mint, ResetMint { treasury, crank: ONE_ORE / 1_000 });
+        let mut busses = [Bus::zeroed(); BUS_COUNT];
+        let mint = apply_reset(&mut config, &mut busses, supply + ONE_ORE / 1_000).unwrap();
+        assert_eq!(mint, ResetMint { treasury, crank: 0 });
+    }
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Mine { bus: usize, difficulty: u32 },
+        Burn { amount: u64 },
+        Reset,
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            4 => (0..BUS_COUNT, prop_oneof![0..12u32, 30..40u32, 60..80u32])
+                .prop_map(|(bus, difficulty)| Op::Mine { bus, difficulty }),
+            1 => (0..ONE_ORE * 10).prop_map(|amount| Op::Burn { amount }),
+            2 => Just(Op::Reset),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_apply_reset_invariants(
+            max_supply in ONE_ORE..ONE_ORE * 1_000,
+            initial_epoch_rewards in ONE_ORE / 1_000..=TARGET_EPOCH_REWARDS,
+            schedule_epochs in 0..64u64,
+            decay_basis_points in 0..=10_000u64,
+            recycle_burns in 0..=1u64,
+            crank_reward in 0..ONE_ORE,
+            ops in vec(op(), 1..300),
+        ) {
+            let mut config = Config::zeroed();
+            config.max_supply = max_supply;
+            config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+            config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+            config.initial_epoch_rewards = initial_epoch_rewards;
+            config.schedule_epochs = schedule_epochs;
+            config.decay_basis_points = decay_basis_points;
+            config.recycle_burns = recycle_burns;
+            config.crank_reward = crank_reward;
+            let mut busses = [Bus::zeroed(); BUS_COUNT];
+            let mut supply = 0u64;
+
+            for op in ops {
+                match op {
+                    // The difficulty is given relative to the min difficulty.
+                    Op::Mine { bus, difficulty } => {
+                        let bus = &mut busses[bus];
+                        let difficulty = config.min_difficulty as u32 + difficulty;
+                        let reward = compute_mine_reward(&config, bus, difficulty).unwrap();
+                        bus.theoretical_rewards =
+                            bus.theoretical_rewards.saturating_add(reward.theoretical);
+                        bus.rewards -= reward.actual;
+                    }
+                    Op::Burn { amount } => {
+                        let amount = amount.min(supply);
+                        supply -= amount;
+                        config.total_burned = config.total_burned.saturating_add(amount);
+                    }
+                    // A failed reset reverts the transaction, so it is applied to copies.
+                    Op::Reset => {
+                        let mut next_config = config;
+                        let mut next_busses = busses;
+                        if let Ok(mint) = apply_reset(&mut next_config, &mut next_busses, supply) {
+                            config = next_config;
+                            busses = next_busses;
+                            supply += mint.treasury + mint.crank;
+                        }
+                    }
+                }
+
+                prop_assert!(config.emitted_supply(supply) <= config.max_supply);
+                prop_assert!(config.min_difficulty >= 1);
+                prop_assert!(config.base_reward_rate >= 1);
+                prop_assert!(config.base_reward_rate <= BUS_EPOCH_REWARDS);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_boost_reward() {
+        // A staker with the whole stake earns the full multiplier.
+        assert_eq!(compute_boost_reward(1_000, 10_000, 50, 50), 1_000);
+        assert_eq!(compute_boost_reward(1_000, 5_000, 50, 50), 500);
+
+        // Otherwise, in proportion to its share.
+        assert_eq!(compute_boost_reward(1_000, 20_000, 25, 100), 500);
+
+        // Empty boosts pay nothing, and bonuses saturate.
+        assert_eq!(compute_boost_reward(1_000, 10_000, 0, 0), 0);
+        assert_eq!(compute_boost_reward(u64::MAX, u64::MAX, 1, 1), u64::MAX);
+    }
+
+    #[test]
+    fn test_split_pool_reward() {
+        assert_eq!(split_pool_reward(100, &[10, 30]), vec![25, 75]);
+        assert_eq!(split_pool_reward(100, &[1, 1, 1]), vec![33, 33, 33]);
+        assert_eq!(split_pool_reward(u64::MAX, &[64, 64]), vec![u64::MAX / 2; 2]);
+        assert_eq!(split_pool_reward(100, &[0, 0]), vec![0, 0]);
+        assert_eq!(split_pool_reward(100, &[]), Vec::<u64>::new());
+    }
+
+    proptest! {
+        #[test]
+        fn test_split_pool_reward_invariants(
+            reward in any::<u64>(),
+            difficulties in vec(1..=64u64, 1..=MAX_POOL_SUBMISSIONS),
+        ) {
+            // Shares never exceed the reward, and at most one unit per member is left over.
+            let shares = split_pool_reward(reward, &difficulties);
+            let total: u128 = shares.iter().map(|s| *s as u128).sum();
+            prop_assert!(total <= reward as u128);
+            prop_assert!(reward as u128 - total < difficulties.len() as u128);
+        }
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_same_decimals() {
+        let upgrade_amount = calculate_upgrade_amount(1_000, 9, 9, 10_000);
+        assert!(upgrade_amount.eq(&1_000));
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_v1() {
+        let upgrade_amount =
+            calculate_upgrade_amount(1_000, TOKEN_DECIMALS_V1, TOKEN_DECIMALS, 10_000);
+        assert!(upgrade_amount.eq(&100_000));
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_fewer_decimals() {
+        let upgrade_amount = calculate_upgrade_amount(1_000, 11, 9, 10_000);
+        assert!(upgrade_amount.eq(&10));
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_ratio() {
+        let upgrade_amount = calculate_upgrade_amount(1_000, 9, 9, 2_500);
+        assert!(upgrade_amount.eq(&250));
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_zero_ratio() {
+        let upgrade_amount = calculate_upgrade_amount(1_000, 9, 9, 0);
+        assert!(upgrade_amount.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_upgrade_amount_max_inputs() {
+        let upgrade_amount = calculate_upgrade_amount(u64::MAX, 0, u8::MAX, u64::MAX);
+        assert!(upgrade_amount.eq(&u64::MAX));
+    }
+}
@@ -11,8 +11,14 @@ use ore_boost_api::state::{reservation_pda, directory_pda};
 
 use crate::{
     consts::*,
+    error::OreError,
+    event::MineEvent,
     instruction::*,
-    state::{bus_pda, config_pda, proof_pda, treasury_pda, Ingredient},
+    logic::{apply_boost, compute_mine_reward},
+    state::{
+        boost_pda, bus_pda, config_pda, pool_member_pda, pool_pda, proof_pda, stake_pda,
+        treasury_pda, Boost, Bus, Config, Proof, Stake,
+    },
 };
 
 /// Builds an auth instruction.
@@ -24,16 +30,52 @@ pub fn auth(proof: Pubkey) -> Instruction {
     }
 }
 
+/// Builds a compute budget instruction setting the transaction's compute unit limit.
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&units.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Builds a compute budget instruction setting the transaction's priority fee, in micro-lamports
+/// per compute unit.
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend_from_slice(&micro_lamports.to_le_bytes());
+    Instruction {
+        program_id: COMPUTE_BUDGET_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
 /// Builds a claim instruction.
 pub fn claim(mint: Pubkey, signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
+    claim_with_program_id(mint, signer, beneficiary, amount, &spl_token::ID)
+}
+
+/// Builds a claim instruction for a mint owned by the given token program.
+pub fn claim_with_program_id(
+    mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
     let proof = proof_pda(mint, signer).0;
-    let ore_proof: (Pubkey, u8) = ore_proof_pda(signer);
+    let ore_proof: (Pubkey, u8) = ore_proof_pda(proof);
 
     let treasury = treasury_pda();
-    let treasury_tokens_address = spl_associated_token_account::get_associated_token_address(
-        &treasury.0,
-        &mint,
-    );
+    let treasury_tokens_address =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            &treasury.0,
+            &mint,
+            token_program_id,
+        );
     let treasury_ore_tokens_address = spl_associated_token_account::get_associated_token_address(
         &treasury.0,
         &ORE_MINT_ADDRESS,
@@ -45,6 +87,7 @@ pub fn claim(mint: Pubkey, signer: Pubkey, beneficiary: Pubkey, amount: u64) ->
             AccountMeta::new(signer, true),
             AccountMeta::new(beneficiary, false),
             AccountMeta::new(proof, false),
+            AccountMeta::new(config_pda(mint).0, false),
             AccountMeta::new(mint, false),
             AccountMeta::new(ore_proof.0, false),
             AccountMeta::new_readonly(treasury.0, false),
@@ -52,7 +95,9 @@ pub fn claim(mint: Pubkey, signer: Pubkey, beneficiary: Pubkey, amount: u64) ->
             AccountMeta::new(treasury_ore_tokens_address, false),
             AccountMeta::new_readonly(ORE_TREASURY_ADDRESS, false),
             AccountMeta::new(ORE_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(*token_program_id, false),
             AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(ore_api::ID, false),
         ],
         data: Claim {
             amount: amount.to_le_bytes(),
@@ -64,7 +109,7 @@ pub fn claim(mint: Pubkey, signer: Pubkey, beneficiary: Pubkey, amount: u64) ->
 /// Builds a close instruction.
 pub fn close(mint: Pubkey, signer: Pubkey) -> Instruction {
     let proof = proof_pda(mint, signer).0;
-    let ore_proof: (Pubkey, u8) = ore_proof_pda(signer);
+    let ore_proof: (Pubkey, u8) = ore_proof_pda(proof);
     
     Instruction {
         program_id: crate::ID,
@@ -72,13 +117,16 @@ pub fn close(mint: Pubkey, signer: Pubkey) -> Instruction {
             AccountMeta::new(signer, true),
             AccountMeta::new(proof, false),
             AccountMeta::new(ore_proof.0, false),
+            AccountMeta::new_readonly(mint, false),
             AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(ore_api::ID, false),
         ],
         data: Close {}.to_bytes(),
     }
 }
 
 /// Builds a mine instruction.
+#[allow(clippy::too_many_arguments)]
 pub fn mine(
     mint: Pubkey,
     signer: Pubkey,
@@ -97,9 +145,9 @@ pub fn mine(
     
     let mut accounts = vec![
         AccountMeta::new(signer, true),
-        AccountMeta::new(MINT_ADDRESS, false),
+        AccountMeta::new(mint, false),
         AccountMeta::new(bus, false),
-        AccountMeta::new_readonly(config, false),
+        AccountMeta::new(config, false),
         AccountMeta::new(proof, false),
         AccountMeta::new(ore_bus, false),
         AccountMeta::new_readonly(ORE_CONFIG_ADDRESS, false),
@@ -109,36 +157,88 @@ pub fn mine(
         AccountMeta::new(ORE_TREASURY_TOKENS_ADDRESS, false),
         AccountMeta::new_readonly(sysvar::instructions::ID, false),
         AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+        AccountMeta::new_readonly(ore_api::ID, false),
+        AccountMeta::new_readonly(ore_boost_api::ID, false),
     ];
 
+    let mut ore_boost_accounts = 0;
     if let Some((boost_address, reservation_address)) = boost_keys {
         accounts.push(AccountMeta::new_readonly(boost_address, false));
         accounts.push(AccountMeta::new(ore_proof_pda(boost_address).0, false));
         accounts.push(AccountMeta::new_readonly(reservation_address, false));
+        ore_boost_accounts = 3;
     }
 
-    let additional_accounts = additional_accounts
+    let additional_accounts: Vec<AccountMeta> = additional_accounts
         .into_iter()
         .map(|pk| AccountMeta::new_readonly(pk, false))
         .collect();
 
     Instruction {
         program_id: crate::ID,
-        accounts: [accounts, additional_accounts].concat(),
         data: Mine {
             digest: solution.d,
             nonce: solution.n,
+            coal_boost_accounts: additional_accounts.len() as u8,
+            ore_boost_accounts,
         }
         .to_bytes(),
+        accounts: [accounts, additional_accounts].concat(),
     }
 }
 
+/// Returns the COAL boost accounts to pass to `mine` as additional accounts, for the stakes of
+/// the authority in the boosts of the given mints.
+pub fn coal_boost_accounts(authority: Pubkey, boost_mints: &[Pubkey]) -> Vec<Pubkey> {
+    boost_mints
+        .iter()
+        .flat_map(|mint| {
+            let boost = boost_pda(*mint).0;
+            [boost, stake_pda(boost, authority).0]
+        })
+        .collect()
+}
+
+/// Previews the mine event for submitting a solution to the bus, using the same reward math as
+/// the mine instruction.
+///
+/// Clients can compare previews across busses to pick the one with the most remaining rewards.
+/// The preview assumes the epoch has not ended yet and that the solution is valid for the ORE
+/// proof's challenge. Fails with `HashTooEasy` if the solution is below the min difficulty.
+///
+/// The COAL boosts the mine passes are given as their boost and the proof authority's stake, and
+/// add their bonus at the given time like they would on chain.
+pub fn preview_mine(
+    config: &Config,
+    bus: &Bus,
+    proof: &Proof,
+    solution: &Solution,
+    boosts: &[(Boost, Stake)],
+    now: i64,
+) -> Result<MineEvent, OreError> {
+    let difficulty = solution.to_hash().difficulty();
+    let reward = compute_mine_reward(config, bus, difficulty)?;
+    let reward_actual = boosts.iter().fold(reward.actual, |reward_actual, (boost, stake)| {
+        apply_boost(reward_actual, reward.actual, boost, stake, now, bus.rewards)
+    });
+    Ok(MineEvent {
+        balance: proof.balance.saturating_add(reward_actual),
+        difficulty: difficulty as u64,
+        reward: reward_actual,
+    })
+}
+
+/// Returns the unix timestamp from which the current epoch can be reset.
+pub fn next_reset_at(config: &Config) -> i64 {
+    config.last_reset_at.saturating_add(EPOCH_DURATION)
+}
+
 /// Builds an open instruction.
 /// signer_info, miner_info, payer_info, proof_info, ore_proof_info, ore_reservation_into, mint_info, system_program, slot_hashes_info
 pub fn open(mint: Pubkey, signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
     let config = config_pda(mint).0;
     let proof_pda: (Pubkey, u8) = proof_pda(mint, signer);
-    let ore_proof_pda = ore_proof_pda(signer);
+    let ore_proof_pda = ore_proof_pda(proof_pda.0);
     let ore_reservation_pda = reservation_pda(ore_proof_pda.0);
     
     Instruction {
@@ -151,9 +251,11 @@ pub fn open(mint: Pubkey, signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instr
             AccountMeta::new(proof_pda.0, false),
             AccountMeta::new(ore_proof_pda.0, false),
             AccountMeta::new(ore_reservation_pda.0, false),
-            AccountMeta::new(MINT_ADDRESS, false),
+            AccountMeta::new(mint, false),
             AccountMeta::new_readonly(solana_program::system_program::ID, false),
             AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(ore_api::ID, false),
+            AccountMeta::new_readonly(ore_boost_api::ID, false),
         ],
         data: Open { proof_bump: proof_pda.1 }.to_bytes(),
     }
@@ -161,6 +263,33 @@ pub fn open(mint: Pubkey, signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instr
 
 /// Builds a reset instruction.
 pub fn reset(mint: Pubkey, signer: Pubkey) -> Instruction {
+    reset_with_program_id(mint, signer, &spl_token::ID)
+}
+
+/// Builds a reset instruction for a mint owned by the given token program.
+pub fn reset_with_program_id(
+    mint: Pubkey,
+    signer: Pubkey,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    reset_with_args(mint, signer, token_program_id, Reset { strict: 0 }, None)
+}
+
+/// Builds a reset instruction that fails with `ResetTooEarly` if the epoch has not ended, so
+/// cranks can tell a landed reset from a no-op.
+pub fn reset_strict(mint: Pubkey, signer: Pubkey, token_program_id: &Pubkey) -> Instruction {
+    reset_with_args(mint, signer, token_program_id, Reset { strict: 1 }, None)
+}
+
+/// Builds a reset instruction with the given args. If a token account of the mint is given, the
+/// first reset of the epoch pays the config's crank reward into it.
+pub fn reset_with_args(
+    mint: Pubkey,
+    signer: Pubkey,
+    token_program_id: &Pubkey,
+    args: Reset,
+    crank_reward_tokens: Option<Pubkey>,
+) -> Instruction {
     let bus_pdas = [
         bus_pda(mint, 0),
         bus_pda(mint, 1),
@@ -172,89 +301,485 @@ pub fn reset(mint: Pubkey, signer: Pubkey) -> Instruction {
         bus_pda(mint, 7),
     ];
     let config_pda = config_pda(mint);
-    let treasury_tokens_address = spl_associated_token_account::get_associated_token_address(
+    let treasury_tokens_address =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            &TREASURY_ADDRESS,
+            &mint,
+            token_program_id,
+        );
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus_pdas[0].0, false),
+        AccountMeta::new(bus_pdas[1].0, false),
+        AccountMeta::new(bus_pdas[2].0, false),
+        AccountMeta::new(bus_pdas[3].0, false),
+        AccountMeta::new(bus_pdas[4].0, false),
+        AccountMeta::new(bus_pdas[5].0, false),
+        AccountMeta::new(bus_pdas[6].0, false),
+        AccountMeta::new(bus_pdas[7].0, false),
+        AccountMeta::new(config_pda.0, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(treasury_tokens_address, false),
+        AccountMeta::new_readonly(*token_program_id, false),
+    ];
+    if let Some(crank_reward_tokens) = crank_reward_tokens {
+        accounts.push(AccountMeta::new(crank_reward_tokens, false));
+    }
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: args.to_bytes(),
+    }
+}
+
+// Build an update instruction.
+pub fn update(mint: Pubkey, signer: Pubkey, miner: Pubkey) -> Instruction {
+    let proof = proof_pda(mint, signer).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: Update {}.to_bytes(),
+    }
+}
+
+/// Builds an upgrade instruction to migrate tokens of the config's migration mint.
+pub fn upgrade(
+    mint: Pubkey,
+    migration_mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+) -> Instruction {
+    upgrade_with_program_id(
+        mint,
+        migration_mint,
+        signer,
+        beneficiary,
+        sender,
+        amount,
+        &spl_token::ID,
+        &spl_token::ID,
+    )
+}
+
+/// Builds an upgrade instruction for mints owned by the given token programs.
+#[allow(clippy::too_many_arguments)]
+pub fn upgrade_with_program_id(
+    mint: Pubkey,
+    migration_mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+    migration_token_program_id: &Pubkey,
+) -> Instruction {
+    let config = config_pda(mint).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(migration_mint, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(*migration_token_program_id, false),
+        ],
+        data: Upgrade {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a redeem instruction to burn COAL for a share of the treasury ORE.
+pub fn redeem(
+    mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+) -> Instruction {
+    redeem_with_program_id(mint, signer, beneficiary, sender, amount, &spl_token::ID)
+}
+
+/// Builds a redeem instruction for a mint owned by the given token program.
+pub fn redeem_with_program_id(
+    mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let config = config_pda(mint).0;
+    let treasury_ore_tokens_address = spl_associated_token_account::get_associated_token_address(
         &TREASURY_ADDRESS,
-        &mint,
+        &ORE_MINT_ADDRESS,
     );
 
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(bus_pdas[0].0, false),
-            AccountMeta::new(bus_pdas[1].0, false),
-            AccountMeta::new(bus_pdas[2].0, false),
-            AccountMeta::new(bus_pdas[3].0, false),
-            AccountMeta::new(bus_pdas[4].0, false),
-            AccountMeta::new(bus_pdas[5].0, false),
-            AccountMeta::new(bus_pdas[6].0, false),
-            AccountMeta::new(bus_pdas[7].0, false),
-            AccountMeta::new(config_pda.0, false),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(config, false),
             AccountMeta::new(mint, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(treasury_ore_tokens_address, false),
+            AccountMeta::new_readonly(*token_program_id, false),
             AccountMeta::new_readonly(spl_token::ID, false),
         ],
-        data: Reset {}.to_bytes(),
+        data: Redeem {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
     }
 }
 
-/// Build a stake instruction.
-pub fn stake(mint: Pubkey, signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
-    let proof = proof_pda(mint, signer).0;
+/// Builds a burn instruction.
+pub fn burn(mint: Pubkey, signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
+    burn_with_program_id(mint, signer, sender, amount, &spl_token::ID)
+}
+
+/// Builds a burn instruction for a mint owned by the given token program.
+pub fn burn_with_program_id(
+    mint: Pubkey,
+    signer: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let config = config_pda(mint).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(proof, false),
+            AccountMeta::new(config, false),
+            AccountMeta::new(mint, false),
             AccountMeta::new(sender, false),
-            AccountMeta::new(TREASURY_TOKENS_ADDRESS, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(*token_program_id, false),
         ],
-        data: Stake {
+        data: Burn {
             amount: amount.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
-// Build an update instruction.
-pub fn update(mint: Pubkey, signer: Pubkey, miner: Pubkey) -> Instruction {
-    let proof = proof_pda(mint, signer).0;
+/// Builds an open pool instruction. The pool's proof is owned by the pool, and the signer submits
+/// hashes for it.
+pub fn open_pool(mint: Pubkey, signer: Pubkey, payer: Pubkey) -> Instruction {
+    let config = config_pda(mint).0;
+    let pool = pool_pda(mint, signer).0;
+    let proof = proof_pda(mint, pool);
+    let ore_proof = ore_proof_pda(proof.0).0;
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(proof.0, false),
+            AccountMeta::new(ore_proof, false),
+            AccountMeta::new(reservation_pda(ore_proof).0, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(ore_api::ID, false),
+            AccountMeta::new_readonly(ore_boost_api::ID, false),
         ],
-        data: Update {}.to_bytes(),
+        data: OpenPool { proof_bump: proof.1 }.to_bytes(),
     }
 }
 
-// Build an upgrade instruction for COAL v1 to v2.
-pub fn upgrade(signer: Pubkey, beneficiary: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
+/// Builds a join pool instruction.
+pub fn join_pool(pool: Pubkey, signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(pool_member_pda(pool, signer).0, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data: JoinPool {}.to_bytes(),
+    }
+}
+
+/// Builds a pool mine instruction, submitting a hash for the pool's proof on behalf of the member.
+/// The signer is the pool authority.
+#[allow(clippy::too_many_arguments)]
+pub fn mine_pool(
+    mint: Pubkey,
+    signer: Pubkey,
+    member: Pubkey,
+    bus: Pubkey,
+    ore_bus: Pubkey,
+    solution: Solution,
+    additional_accounts: Vec<Pubkey>,
+    boost_keys: Option<(Pubkey, Pubkey)>,
+) -> Instruction {
+    let pool = pool_pda(mint, signer).0;
+    let ix = mine(mint, signer, pool, bus, ore_bus, solution, additional_accounts, boost_keys);
+    let args = Mine::try_from_bytes(&ix.data[1..]).unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: [
+            vec![
+                AccountMeta::new(pool, false),
+                AccountMeta::new(pool_member_pda(pool, member).0, false),
+            ],
+            ix.accounts,
+        ]
+        .concat(),
+        data: MinePool {
+            digest: args.digest,
+            nonce: args.nonce,
+            coal_boost_accounts: args.coal_boost_accounts,
+            ore_boost_accounts: args.ore_boost_accounts,
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a pool submission instruction, mining with the best of the members' solutions and
+/// splitting the reward between the members. The signer is the pool authority.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_pool(
+    mint: Pubkey,
+    signer: Pubkey,
+    bus: Pubkey,
+    ore_bus: Pubkey,
+    solutions: &[(Pubkey, Solution)],
+    additional_accounts: Vec<Pubkey>,
+    boost_keys: Option<(Pubkey, Pubkey)>,
+) -> Instruction {
+    let pool = pool_pda(mint, signer).0;
+    let solution = solutions.first().map_or(Solution::new([0; 16], [0; 8]), |(_, s)| *s);
+    let ix = mine(mint, signer, pool, bus, ore_bus, solution, additional_accounts, boost_keys);
+    let args = Mine::try_from_bytes(&ix.data[1..]).unwrap();
+    let mut data = SubmitPool {
+        coal_boost_accounts: args.coal_boost_accounts,
+        ore_boost_accounts: args.ore_boost_accounts,
+        members: solutions.len() as u8,
+        solutions: [[0; 24]; MAX_POOL_SUBMISSIONS],
+    };
+    for (i, (_, solution)) in solutions.iter().take(MAX_POOL_SUBMISSIONS).enumerate() {
+        data.solutions[i] = solution.to_bytes();
+    }
+    let members = solutions
+        .iter()
+        .map(|(member, _)| AccountMeta::new(pool_member_pda(pool, *member).0, false));
+    Instruction {
+        program_id: crate::ID,
+        accounts: [vec![AccountMeta::new(pool, false)], ix.accounts, members.collect()].concat(),
+        data: data.to_bytes(),
+    }
+}
+
+/// Builds a pool claim instruction, paying out the signer's part of the pool's rewards.
+pub fn claim_pool(
+    mint: Pubkey,
+    signer: Pubkey,
+    pool: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+) -> Instruction {
+    claim_pool_with_program_id(mint, signer, pool, beneficiary, amount, &spl_token::ID)
+}
+
+/// Builds a pool claim instruction for a mint owned by the given token program.
+pub fn claim_pool_with_program_id(
+    mint: Pubkey,
+    signer: Pubkey,
+    pool: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let treasury = treasury_pda().0;
+    let treasury_tokens_address =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            &treasury,
+            &mint,
+            token_program_id,
+        );
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(beneficiary, false),
-            AccountMeta::new(MINT_ADDRESS, false),
-            AccountMeta::new(MINT_V1_ADDRESS, false),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(pool_member_pda(pool, signer).0, false),
+            AccountMeta::new(proof_pda(mint, pool).0, false),
+            AccountMeta::new(config_pda(mint).0, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(treasury, false),
+            AccountMeta::new(treasury_tokens_address, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+        data: ClaimPool {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds an open stake instruction for the boost of the mint.
+pub fn open_stake(mint: Pubkey, signer: Pubkey) -> Instruction {
+    let boost = boost_pda(mint).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(boost, false),
+            AccountMeta::new(stake_pda(boost, signer).0, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data: OpenStake {}.to_bytes(),
+    }
+}
+
+/// Builds a deposit instruction, staking tokens of the mint in its boost.
+pub fn deposit(
+    mint: Pubkey,
+    signer: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let boost = boost_pda(mint).0;
+    let boost_tokens = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &boost,
+        &mint,
+        token_program_id,
+    );
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(boost, false),
+            AccountMeta::new(stake_pda(boost, signer).0, false),
             AccountMeta::new(sender, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new(boost_tokens, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(*token_program_id, false),
         ],
-        data: Upgrade {
+        data: Deposit {
+            amount: amount.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a withdraw instruction, unstaking tokens of the mint from its boost.
+pub fn withdraw(
+    mint: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let boost = boost_pda(mint).0;
+    let boost_tokens = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &boost,
+        &mint,
+        token_program_id,
+    );
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(boost, false),
+            AccountMeta::new(stake_pda(boost, signer).0, false),
+            AccountMeta::new(boost_tokens, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+        ],
+        data: Withdraw {
             amount: amount.to_le_bytes(),
         }
         .to_bytes(),
     }
 }
 
+/// Builds a migrate instruction, which grows an account created under an earlier layout to the
+/// current one. The signer pays the rent for the extra space.
+pub fn migrate(signer: Pubkey, address: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(address, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data: Migrate {}.to_bytes(),
+    }
+}
+
 /// Builds an initialize instruction.
 pub fn initialize(signer: Pubkey, mint_noise: [u8; 16]) -> Instruction {
-    let mint_pda = Pubkey::find_program_address(&[MINT, mint_noise.as_slice()], &crate::ID);
+    initialize_with_program_id(signer, mint_noise, &spl_token::ID)
+}
+
+/// Builds an initialize instruction for a mint owned by the given token program.
+///
+/// Token-2022 mints are created with the metadata pointer and token metadata extensions
+/// instead of a Metaplex metadata account.
+pub fn initialize_with_program_id(
+    signer: Pubkey,
+    mint_noise: [u8; 16],
+    token_program_id: &Pubkey,
+) -> Instruction {
+    initialize_with_args(
+        signer,
+        token_program_id,
+        Initialize {
+            mint_noise,
+            treasury_bump: 0,
+            mint_bump: 0,
+            migration_mint: MINT_V1_ADDRESS,
+            metadata_name: to_padded_bytes(METADATA_NAME),
+            metadata_symbol: to_padded_bytes(METADATA_SYMBOL),
+            metadata_uri: to_padded_bytes(METADATA_URI),
+            burned_ingredient_mint: Pubkey::default(),
+            burned_ingredient_ratio: 0f64.to_le_bytes(),
+            wrapped_ingredient_mint: Pubkey::default(),
+            wrapped_ingredient_ratio: 0f64.to_le_bytes(),
+            max_supply: (ONE_ORE * 21_000_000).to_le_bytes(),
+            schedule_epochs: 17_280u64.to_le_bytes(), // 8 weeks
+            decay_basis_points: 2000u64.to_le_bytes(), // 20%
+            recycle_burns: 0u64.to_le_bytes(),
+            migration_ratio: 10_000u64.to_le_bytes(),
+            migration_deadline: 0u64.to_le_bytes(),
+            crank_reward: 0u64.to_le_bytes(),
+        },
+    )
+}
+
+/// Builds an initialize instruction with the given args for a mint owned by the given token
+/// program. The treasury and mint bumps are derived from the mint noise.
+pub fn initialize_with_args(
+    signer: Pubkey,
+    token_program_id: &Pubkey,
+    args: Initialize,
+) -> Instruction {
+    let mint_pda = Pubkey::find_program_address(&[MINT, args.mint_noise.as_slice()], &crate::ID);
     let bus_pdas = [
         bus_pda(mint_pda.0, 0),
         bus_pda(mint_pda.0, 1),
@@ -267,10 +792,12 @@ pub fn initialize(signer: Pubkey, mint_noise: [u8; 16]) -> Instruction {
     ];
     let config_pda = config_pda(mint_pda.0);
     let treasury_pda = treasury_pda();
-    let treasury_tokens_address = spl_associated_token_account::get_associated_token_address(
-        &treasury_pda.0,
-        &mint_pda.0,
-    );
+    let treasury_tokens_address =
+        spl_associated_token_account::get_associated_token_address_with_program_id(
+            &treasury_pda.0,
+            &mint_pda.0,
+            token_program_id,
+        );
     let ore_treasury_tokens_address = spl_associated_token_account::get_associated_token_address(
         &treasury_pda.0,
         &ORE_MINT_ADDRESS,
@@ -304,31 +831,252 @@ pub fn initialize(signer: Pubkey, mint_noise: [u8; 16]) -> Instruction {
             AccountMeta::new(treasury_tokens_address, false),
             AccountMeta::new(ore_treasury_tokens_address, false),
             AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(*token_program_id, false),
             AccountMeta::new_readonly(spl_token::ID, false),
             AccountMeta::new_readonly(spl_associated_token_account::ID, false),
             AccountMeta::new_readonly(mpl_token_metadata::ID, false),
             AccountMeta::new_readonly(sysvar::rent::ID, false),
         ],
         data: Initialize {
-            mint_noise,
-            treasury_bump: treasury_pda.1 as u64,
-            mint_bump: mint_pda.1 as u64,
-            migration_mint: MINT_V1_ADDRESS,
-            metadata_name: METADATA_NAME.to_string().as_bytes()[..32].try_into().unwrap(),
-            metadata_symbol: METADATA_SYMBOL.to_string().as_bytes()[..8].try_into().unwrap(),
-            metadata_uri: METADATA_URI.to_string().as_bytes()[..128].try_into().unwrap(),
-            burned_ingredient: Ingredient {
-                mint: Pubkey::default(),
-                ratio: 0.0,
-            },
-            wrapped_ingredient: Ingredient {
-                mint: Pubkey::default(),
-                ratio: 0.0,
-            },
-            max_supply: ONE_ORE * 21_000_000,
-            schedule_epochs: 17_280, // 8 weeks
-            decay_basis_points: 2000, // 20%
+            treasury_bump: treasury_pda.1,
+            mint_bump: mint_pda.1,
+            ..args
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a set migration instruction.
+pub fn set_migration(
+    signer: Pubkey,
+    mint: Pubkey,
+    migration_mint: Pubkey,
+    migration_ratio: u64,
+    migration_deadline: u64,
+) -> Instruction {
+    let config = config_pda(mint).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: SetMigration {
+            migration_mint,
+            migration_ratio: migration_ratio.to_le_bytes(),
+            migration_deadline: migration_deadline.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Builds a set mine policy instruction.
+pub fn set_mine_policy(
+    signer: Pubkey,
+    mint: Pubkey,
+    mine_policy: u64,
+    mine_cpi_allowlist: [Pubkey; MINE_CPI_ALLOWLIST_SIZE],
+) -> Instruction {
+    let config = config_pda(mint).0;
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: SetMinePolicy {
+            mine_policy: mine_policy.to_le_bytes(),
+            mine_cpi_allowlist,
         }
         .to_bytes(),
     }
 }
+
+/// Builds a new boost instruction for stakers of the mint.
+pub fn new_boost(
+    signer: Pubkey,
+    mint: Pubkey,
+    multiplier: u64,
+    expires_at: i64,
+    token_program_id: &Pubkey,
+) -> Instruction {
+    let boost = boost_pda(mint).0;
+    let boost_tokens = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &boost,
+        &mint,
+        token_program_id,
+    );
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(boost, false),
+            AccountMeta::new(boost_tokens, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(*token_program_id, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+        data: NewBoost {
+            multiplier: multiplier.to_le_bytes(),
+            expires_at: expires_at.to_le_bytes(),
+        }
+        .to_bytes(),
+    }
+}
+
+/// Copies a string into a fixed-size, zero-padded byte array, truncating if it is too long.
+pub fn to_padded_bytes<const N: usize>(value: &str) -> [u8; N] {
+    let mut bytes = [0; N];
+    let len = value.len().min(N);
+    bytes[..len].copy_from_slice(&value.as_bytes()[..len]);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use drillx::Solution;
+    use steel::*;
+
+    use crate::{
+        consts::{
+            BUS_COUNT, INITIAL_BASE_REWARD_RATE, MINT_ADDRESS, MINT_NOISE, ONE_ORE,
+            TARGET_EPOCH_REWARDS,
+        },
+        error::OreError,
+        instruction::{Initialize, Mine, MinePool, OreInstruction},
+        sdk::{
+            initialize, mine, mine_pool, preview_mine, set_compute_unit_limit,
+            set_compute_unit_price,
+        },
+        state::{pool_member_pda, pool_pda, proof_pda, Boost, Bus, Config, Proof, Stake},
+    };
+
+    fn solve(challenge: [u8; 32], difficulty: u32) -> Solution {
+        let mut memory = drillx::equix::SolverMemory::new();
+        for nonce in 0u64.. {
+            let nonce = nonce.to_le_bytes();
+            for hash in drillx::hashes_with_memory(&mut memory, &challenge, &nonce) {
+                if hash.difficulty().eq(&difficulty) {
+                    return Solution::new(hash.d, nonce);
+                }
+            }
+        }
+        unreachable!()
+    }
+
+    #[test]
+    fn test_preview_mine() {
+        let mut config = Config::zeroed();
+        config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+        config.min_difficulty = 2;
+        config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+        let mut bus = Bus::zeroed();
+        bus.rewards = TARGET_EPOCH_REWARDS / BUS_COUNT as u64;
+        let mut proof = Proof::zeroed();
+        proof.balance = 10;
+
+        let solution = solve([1; 32], 4);
+        let event = preview_mine(&config, &bus, &proof, &solution, &[], 0).unwrap();
+        assert_eq!(event.difficulty, 4);
+        assert_eq!(event.reward, INITIAL_BASE_REWARD_RATE * 4);
+        assert_eq!(event.balance, 10 + INITIAL_BASE_REWARD_RATE * 4);
+
+        // An almost empty bus caps the reward.
+        bus.rewards = 1;
+        let event = preview_mine(&config, &bus, &proof, &solution, &[], 0).unwrap();
+        assert_eq!(event.reward, 1);
+
+        // Active boosts with settled stake add their bonus, up to the bus rewards.
+        bus.rewards = TARGET_EPOCH_REWARDS / BUS_COUNT as u64;
+        let boost = Boost {
+            mint: Pubkey::new_unique(),
+            multiplier: 5_000,
+            expires_at: 1_000,
+            total_stake: 4,
+            bump: 0,
+        };
+        let stake = Stake {
+            authority: Pubkey::new_unique(),
+            boost: Pubkey::new_unique(),
+            balance: 2,
+            last_deposit_at: 0,
+            bump: 0,
+        };
+        let reward = INITIAL_BASE_REWARD_RATE * 4;
+        let boosts = [(boost, stake), (boost, stake)];
+        let event = preview_mine(&config, &bus, &proof, &solution, &boosts, 100).unwrap();
+        assert_eq!(event.reward, reward + reward / 2);
+        assert_eq!(event.balance, 10 + reward + reward / 2);
+        let event = preview_mine(&config, &bus, &proof, &solution, &boosts, 1_000).unwrap();
+        assert_eq!(event.reward, reward);
+        bus.rewards = reward + 1;
+        let event = preview_mine(&config, &bus, &proof, &solution, &boosts, 100).unwrap();
+        assert_eq!(event.reward, reward + 1);
+
+        // Hashes below the min difficulty are rejected rather than previewed.
+        let solution = solve([1; 32], 1);
+        assert_eq!(
+            preview_mine(&config, &bus, &proof, &solution, &[], 0),
+            Err(OreError::HashTooEasy)
+        );
+    }
+
+    #[test]
+    fn test_mine_account_counts() {
+        let signer = Pubkey::new_unique();
+        let solution = Solution::new([0; 16], [0; 8]);
+        let ix = mine(MINT_ADDRESS, signer, signer, signer, signer, solution, vec![], None);
+        let args = Mine::try_from_bytes(&ix.data[1..]).unwrap();
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!((args.coal_boost_accounts, args.ore_boost_accounts), (0, 0));
+
+        let boost_keys = Some((Pubkey::new_unique(), Pubkey::new_unique()));
+        let additional_accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = mine(MINT_ADDRESS, signer, signer, signer, signer, solution, additional_accounts, boost_keys);
+        let args = Mine::try_from_bytes(&ix.data[1..]).unwrap();
+        assert_eq!(ix.accounts.len(), 20);
+        assert_eq!((args.coal_boost_accounts, args.ore_boost_accounts), (2, 3));
+    }
+
+    #[test]
+    fn test_mine_pool_accounts() {
+        let (signer, member) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let solution = Solution::new([1; 16], [2; 8]);
+        let ix = mine_pool(MINT_ADDRESS, signer, member, signer, signer, solution, vec![], None);
+        let pool = pool_pda(MINT_ADDRESS, signer).0;
+        let args = MinePool::try_from_bytes(&ix.data[1..]).unwrap();
+        assert_eq!(ix.data[0], OreInstruction::MinePool as u8);
+        assert_eq!((args.digest, args.nonce), (solution.d, solution.n));
+        assert_eq!(ix.accounts.len(), 17);
+        assert_eq!(ix.accounts[0].pubkey, pool);
+        assert_eq!(ix.accounts[1].pubkey, pool_member_pda(pool, member).0);
+        assert_eq!(ix.accounts[2].pubkey, signer);
+        assert_eq!(ix.accounts[6].pubkey, proof_pda(MINT_ADDRESS, pool).0);
+    }
+
+    #[test]
+    fn test_compute_budget() {
+        use solana_sdk::compute_budget::ComputeBudgetInstruction;
+        assert_eq!(
+            set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000)
+        );
+        assert_eq!(
+            set_compute_unit_price(5_000),
+            ComputeBudgetInstruction::set_compute_unit_price(5_000)
+        );
+    }
+
+    #[test]
+    fn test_initialize_args() {
+        // Instruction data is only byte aligned once the discriminator is stripped.
+        let ix = initialize(Pubkey::new_unique(), MINT_NOISE);
+        let args = Initialize::try_from_bytes(&ix.data[1..]).unwrap();
+        assert_eq!(args.mint_noise, MINT_NOISE);
+        assert_eq!(u64::from_le_bytes(args.max_supply), ONE_ORE * 21_000_000);
+        assert_eq!(f64::from_le_bytes(args.wrapped_ingredient_ratio), 0.0);
+    }
+}
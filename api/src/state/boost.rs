@@ -0,0 +1,34 @@
+use steel::*;
+
+use super::OreAccount;
+
+/// Boost accounts pay stakers of a token a bonus on their mining rewards. Each staker earns the
+/// multiplier in proportion to its share of the boost's total stake.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Boost {
+    /// The mint of the token staked in this boost.
+    pub mint: Pubkey,
+
+    /// The bonus paid to a staker holding all of the boost's stake, in basis points of the mining
+    /// reward.
+    pub multiplier: u64,
+
+    /// The time after which the boost stops paying bonuses. Stake can still be withdrawn.
+    pub expires_at: i64,
+
+    /// The total quantity of tokens staked in this boost.
+    pub total_stake: u64,
+
+    /// The bump for the boost account.
+    pub bump: u64,
+}
+
+impl Boost {
+    /// Returns true if the boost pays bonuses at the given time.
+    pub fn is_active(&self, now: i64) -> bool {
+        now.lt(&self.expires_at)
+    }
+}
+
+account!(OreAccount, Boost);
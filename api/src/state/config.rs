@@ -1,5 +1,7 @@
 use steel::*;
 
+use crate::consts::MINE_CPI_ALLOWLIST_SIZE;
+
 use super::OreAccount;
 
 #[repr(C)]
@@ -16,6 +18,7 @@ pub struct Config {
     pub mint: Pubkey,
 
     /// The mint of the migration token.
+    /// If this is the default pubkey, migration is disabled.
     pub migration_mint: Pubkey,
 
     /// Max supply of the token.
@@ -54,6 +57,42 @@ pub struct Config {
 
     /// An ingredient that is burned in the treasury.
     pub burned_ingredient: Ingredient,
+
+    /// The total lifetime COAL burned in exchange for treasury ORE.
+    pub total_redeemed: u64,
+
+    /// The total lifetime ORE paid out of the treasury to redeemers.
+    pub total_ore_redeemed: u64,
+
+    /// The total lifetime COAL burned through the program.
+    pub total_burned: u64,
+
+    /// Whether burned tokens free up room under the max supply for future emissions.
+    /// If this is 0, burned tokens still count against the max supply.
+    pub recycle_burns: u64,
+
+    /// The number of tokens minted per migrated token, in basis points (10000 = 1:1).
+    pub migration_ratio: u64,
+
+    /// The last epoch in which tokens can be migrated.
+    /// If this is 0, migration does not expire.
+    pub migration_deadline: u64,
+
+    /// The COAL minted to the caller of the first successful reset each epoch.
+    /// If this is 0, resets are not rewarded.
+    pub crank_reward: u64,
+
+    /// Bit flags of the anti-cheat checks applied to mine instructions, see `MINE_POLICY_*`.
+    /// If this is 0, no checks are applied.
+    pub mine_policy: u64,
+
+    /// The programs allowed to mine by CPI when `MINE_POLICY_CPI_ALLOWLIST` is set.
+    /// Unused entries are the default pubkey.
+    pub mine_cpi_allowlist: [Pubkey; MINE_CPI_ALLOWLIST_SIZE],
+
+    /// The ORE swept into the treasury from this mint's proofs and not yet redeemed.
+    /// The treasury's ORE account is shared by every mint, so redeemers are paid out of this.
+    pub ore_balance: u64,
 }
 
 impl Config {
@@ -77,6 +116,20 @@ impl Config {
         // Apply decay to target rewards
         self.initial_epoch_rewards.saturating_mul(remaining) / 10_000
     }
+
+    /// Returns whether tokens of the migration mint can currently be upgraded.
+    pub fn is_migration_open(&self) -> bool {
+        self.migration_mint.ne(&Pubkey::default())
+            && (self.migration_deadline == 0 || self.current_epoch <= self.migration_deadline)
+    }
+
+    /// Returns the supply counted against the max supply, given the current supply of the mint.
+    pub fn emitted_supply(&self, supply: u64) -> u64 {
+        if self.recycle_burns == 0 {
+            return supply.saturating_add(self.total_burned);
+        }
+        supply
+    }
 }
 
 account!(OreAccount, Config);
@@ -1,11 +1,19 @@
+mod boost;
 mod bus;
 mod config;
+mod pool;
+mod pool_member;
 mod proof;
+mod stake;
 mod treasury;
 
+pub use boost::*;
 pub use bus::*;
 pub use config::*;
+pub use pool::*;
+pub use pool_member::*;
 pub use proof::*;
+pub use stake::*;
 pub use treasury::*;
 
 use steel::*;
@@ -19,6 +27,15 @@ pub enum OreAccount {
     Config = 101,
     Proof = 102,
     Treasury = 103,
+    Pool = 104,
+    PoolMember = 105,
+    Boost = 106,
+    Stake = 107,
+}
+
+/// Derive the PDA of a boost account.
+pub fn boost_pda(mint: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOOST, mint.as_ref()], &crate::id())
 }
 
 /// Fetch the PDA of a bus account.
@@ -31,11 +48,26 @@ pub fn config_pda(mint: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[CONFIG, mint.as_ref()], &crate::id())
 }
 
+/// Derive the PDA of a pool account.
+pub fn pool_pda(mint: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL, mint.as_ref(), authority.as_ref()], &crate::id())
+}
+
+/// Derive the PDA of a pool member account.
+pub fn pool_member_pda(pool: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_MEMBER, pool.as_ref(), authority.as_ref()], &crate::id())
+}
+
 /// Derive the PDA of a proof account.
 pub fn proof_pda(mint: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[PROOF, mint.as_ref(), authority.as_ref()], &crate::id())
 }
 
+/// Derive the PDA of a stake account.
+pub fn stake_pda(boost: Pubkey, authority: Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE, boost.as_ref(), authority.as_ref()], &crate::id())
+}
+
 /// Derive the PDA of the treasury account.
 pub fn treasury_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[TREASURY], &crate::id())
@@ -0,0 +1,29 @@
+use steel::*;
+
+use super::OreAccount;
+
+/// Pool accounts let one operator mine on behalf of many members. The pool is the authority of
+/// its own proof, so the proof's rewards can only leave through the pool's accounting.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Pool {
+    /// The signer allowed to submit hashes for the pool, usually a PDA of the pool program.
+    pub authority: Pubkey,
+
+    /// The proof this pool mines with.
+    pub proof: Pubkey,
+
+    /// The number of members that have joined the pool.
+    pub total_members: u64,
+
+    /// The total lifetime hashes submitted by the pool.
+    pub total_hashes: u64,
+
+    /// The sum of the difficulties of every hash submitted by the pool.
+    pub total_difficulty: u64,
+
+    /// The bump for the pool account.
+    pub bump: u64,
+}
+
+account!(OreAccount, Pool);
@@ -0,0 +1,65 @@
+use std::ops::Range;
+
+use steel::*;
+
+use crate::consts::POOL_MEMBER_NONCES;
+
+use super::OreAccount;
+
+/// Pool member accounts track the shares a member has contributed to a pool. Every hash the pool
+/// submits on a member's behalf counts as one share, weighted by its difficulty, and earns the
+/// member a claim on the pool proof's balance.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct PoolMember {
+    /// The member this account tracks.
+    pub authority: Pubkey,
+
+    /// The pool this member belongs to.
+    pub pool: Pubkey,
+
+    /// The total lifetime hashes submitted on behalf of this member.
+    pub total_hashes: u64,
+
+    /// The sum of the difficulties of every hash submitted on behalf of this member.
+    pub total_difficulty: u64,
+
+    /// The bump for the pool member account.
+    pub bump: u64,
+
+    /// The quantity of tokens this member can claim from the pool's proof.
+    pub balance: u64,
+
+    /// The total lifetime rewards attributed to this member.
+    pub total_rewards: u64,
+
+    /// The order in which this member joined the pool, which reserves its nonce range.
+    pub id: u64,
+}
+
+impl PoolMember {
+    /// Returns the nonces this member mines the pool's challenge with.
+    pub fn nonce_range(&self) -> Range<u64> {
+        let start = self.id.saturating_mul(POOL_MEMBER_NONCES);
+        start..start.saturating_add(POOL_MEMBER_NONCES)
+    }
+
+    /// Returns whether the nonce of a solution is in this member's range.
+    pub fn owns_nonce(&self, nonce: [u8; 8]) -> bool {
+        self.nonce_range().contains(&u64::from_le_bytes(nonce))
+    }
+
+    /// Records a hash of the given difficulty submitted on behalf of this member.
+    pub fn record_share(&mut self, difficulty: u64) {
+        self.total_hashes = self.total_hashes.saturating_add(1);
+        self.total_difficulty = self.total_difficulty.saturating_add(difficulty);
+    }
+
+    /// Credits the member with its part of a pool reward.
+    pub fn credit(&mut self, reward: u64) {
+        self.balance = self.balance.saturating_add(reward);
+        self.total_rewards = self.total_rewards.saturating_add(reward);
+    }
+}
+
+account!(OreAccount, PoolMember);
@@ -27,6 +27,9 @@ pub struct Proof {
 
     /// The bump for the proof account.
     pub bump: u64,
+
+    /// The slot of the last hash this proof submitted.
+    pub last_mine_slot: u64,
 }
 
 account!(OreAccount, Proof);
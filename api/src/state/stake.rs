@@ -0,0 +1,35 @@
+use steel::*;
+
+use crate::consts::ONE_MINUTE;
+
+use super::OreAccount;
+
+/// Stake accounts track the tokens a user has staked in a boost.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Stake {
+    /// The signer authorized to use this stake. Its proof earns the boost's bonus.
+    pub authority: Pubkey,
+
+    /// The boost this stake belongs to.
+    pub boost: Pubkey,
+
+    /// The quantity of tokens staked.
+    pub balance: u64,
+
+    /// The last time tokens were deposited into this account.
+    pub last_deposit_at: i64,
+
+    /// The bump for the stake account.
+    pub bump: u64,
+}
+
+impl Stake {
+    /// Returns true if the stake earns bonuses at the given time. Deposits must have settled for
+    /// a minute, so stake cannot be deposited for a single hash and withdrawn right after.
+    pub fn is_settled(&self, now: i64) -> bool {
+        self.last_deposit_at.saturating_add(ONE_MINUTE).le(&now)
+    }
+}
+
+account!(OreAccount, Stake);
@@ -0,0 +1,299 @@
+use drillx::Solution;
+use ore_api::{consts::BUS_ADDRESSES as ORE_BUS_ADDRESSES, state::proof_pda as ore_proof_pda};
+use steel::*;
+
+use crate::{
+    consts::*,
+    sdk::{
+        auth, claim_with_program_id, coal_boost_accounts, mine, reset_with_program_id,
+        set_compute_unit_limit, set_compute_unit_price,
+    },
+    state::{bus_pda, proof_pda, Bus},
+};
+
+/// How a mine transaction picks the bus it submits to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BusSelection {
+    /// Submits to the bus with this id.
+    Id(u8),
+
+    /// Submits to the bus that has paid out the least this epoch, which has seen the fewest
+    /// competing mines and is the least likely to be write-locked.
+    LeastContention([Bus; BUS_COUNT]),
+
+    /// Submits to the bus with the most rewards left to pay out this epoch.
+    MostRewards([Bus; BUS_COUNT]),
+}
+
+impl BusSelection {
+    /// Returns the id of the selected bus. Ties go to the lowest id.
+    pub fn select(&self) -> u8 {
+        match self {
+            BusSelection::Id(id) => *id,
+            BusSelection::LeastContention(busses) => busses
+                .iter()
+                .min_by_key(|bus| (bus.theoretical_rewards, bus.id))
+                .map_or(0, |bus| bus.id as u8),
+            BusSelection::MostRewards(busses) => busses
+                .iter()
+                .max_by_key(|bus| (bus.rewards, std::cmp::Reverse(bus.id)))
+                .map_or(0, |bus| bus.id as u8),
+        }
+    }
+}
+
+/// Assembles the full instruction list of a mine transaction.
+///
+/// The list sets a compute unit limit sized for the instructions it contains, an optional
+/// priority fee, the auth noops for the proofs, an optional reset, the mine, and an optional
+/// claim of the proof's balance.
+#[derive(Clone, Debug)]
+pub struct MineTxBuilder {
+    mint: Pubkey,
+    signer: Pubkey,
+    authority: Pubkey,
+    solution: Solution,
+    bus: BusSelection,
+    priority_fee: u64,
+    mine_policy: u64,
+    coal_boosts: Vec<Pubkey>,
+    ore_boost_keys: Option<(Pubkey, Pubkey)>,
+    reset: bool,
+    claim: Option<(Pubkey, u64)>,
+    token_program_id: Pubkey,
+    compute_unit_limit: Option<u32>,
+}
+
+impl MineTxBuilder {
+    /// Starts a transaction mining the solution with the signer's own proof on bus 0.
+    pub fn new(mint: Pubkey, signer: Pubkey, solution: Solution) -> Self {
+        Self {
+            mint,
+            signer,
+            authority: signer,
+            solution,
+            bus: BusSelection::Id(0),
+            priority_fee: 0,
+            mine_policy: 0,
+            coal_boosts: vec![],
+            ore_boost_keys: None,
+            reset: false,
+            claim: None,
+            token_program_id: spl_token::ID,
+            compute_unit_limit: None,
+        }
+    }
+
+    /// Mines with the proof of another authority, which has the signer as its miner.
+    pub fn authority(mut self, authority: Pubkey) -> Self {
+        self.authority = authority;
+        self
+    }
+
+    /// Sets how the bus is picked.
+    pub fn bus(mut self, bus: BusSelection) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Sets the priority fee, in micro-lamports per compute unit.
+    pub fn priority_fee(mut self, micro_lamports: u64) -> Self {
+        self.priority_fee = micro_lamports;
+        self
+    }
+
+    /// Authenticates the COAL proof as well when the config's mine policy requires it.
+    pub fn mine_policy(mut self, mine_policy: u64) -> Self {
+        self.mine_policy = mine_policy;
+        self
+    }
+
+    /// Applies the COAL boosts of these mints, using the authority's stake in each.
+    pub fn coal_boosts(mut self, boost_mints: &[Pubkey]) -> Self {
+        self.coal_boosts = boost_mints.to_vec();
+        self
+    }
+
+    /// Passes the ORE boost and its stake account through to the ORE mine.
+    pub fn ore_boost_keys(mut self, boost_keys: Option<(Pubkey, Pubkey)>) -> Self {
+        self.ore_boost_keys = boost_keys;
+        self
+    }
+
+    /// Resets the epoch before mining. The reset is not strict, so the mine still lands if
+    /// another miner resets first.
+    pub fn reset(mut self, reset: bool) -> Self {
+        self.reset = reset;
+        self
+    }
+
+    /// Claims the amount from the proof to the beneficiary after mining. The claim must be signed
+    /// by the authority.
+    pub fn claim(mut self, beneficiary: Pubkey, amount: u64) -> Self {
+        self.claim = Some((beneficiary, amount));
+        self
+    }
+
+    /// Sets the token program of the mint, used by the reset and the claim.
+    pub fn token_program_id(mut self, token_program_id: Pubkey) -> Self {
+        self.token_program_id = token_program_id;
+        self
+    }
+
+    /// Overrides the compute unit limit sized from the budgeted cost of each instruction.
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Returns the compute unit limit the transaction requests: the budgeted cost of its
+    /// instructions plus headroom.
+    pub fn compute_units(&self) -> u32 {
+        if let Some(units) = self.compute_unit_limit {
+            return units;
+        }
+        let mut units = MINE_COMPUTE_UNITS
+            + ORE_MINE_COMPUTE_UNITS
+            + COAL_BOOST_COMPUTE_UNITS * self.coal_boosts.len() as u32;
+        if self.ore_boost_keys.is_some() {
+            units += ORE_BOOST_COMPUTE_UNITS;
+        }
+        if self.reset {
+            units += RESET_COMPUTE_UNITS;
+        }
+        if self.claim.is_some() {
+            units += CLAIM_COMPUTE_UNITS + ORE_CLAIM_COMPUTE_UNITS;
+        }
+        let headroom = units as u64 * COMPUTE_UNIT_HEADROOM_BPS as u64 / 10_000;
+        units + headroom as u32 + TX_OVERHEAD_COMPUTE_UNITS
+    }
+
+    /// Builds the instructions of the transaction, in order.
+    pub fn build(&self) -> Vec<Instruction> {
+        let proof = proof_pda(self.mint, self.authority).0;
+        let bus_id = self.bus.select();
+
+        // Set the compute budget.
+        let mut ixs = vec![set_compute_unit_limit(self.compute_units())];
+        if self.priority_fee > 0 {
+            ixs.push(set_compute_unit_price(self.priority_fee));
+        }
+
+        // Authenticate the proofs.
+        if self.mine_policy & MINE_POLICY_REQUIRE_AUTH != 0 {
+            ixs.push(auth(proof));
+        }
+        ixs.push(auth(ore_proof_pda(proof).0));
+
+        if self.reset {
+            ixs.push(reset_with_program_id(
+                self.mint,
+                self.signer,
+                &self.token_program_id,
+            ));
+        }
+        ixs.push(mine(
+            self.mint,
+            self.signer,
+            self.authority,
+            bus_pda(self.mint, bus_id).0,
+            ORE_BUS_ADDRESSES[bus_id as usize],
+            self.solution,
+            coal_boost_accounts(self.authority, &self.coal_boosts),
+            self.ore_boost_keys,
+        ));
+        if let Some((beneficiary, amount)) = self.claim {
+            ixs.push(claim_with_program_id(
+                self.mint,
+                self.authority,
+                beneficiary,
+                amount,
+                &self.token_program_id,
+            ));
+        }
+        ixs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use drillx::Solution;
+    use steel::*;
+
+    use crate::{
+        consts::*,
+        instruction::OreInstruction,
+        state::{bus_pda, proof_pda, Bus},
+        tx::{BusSelection, MineTxBuilder},
+    };
+
+    fn busses(rewards: [u64; BUS_COUNT], theoretical_rewards: [u64; BUS_COUNT]) -> [Bus; BUS_COUNT] {
+        std::array::from_fn(|id| Bus {
+            id: id as u64,
+            rewards: rewards[id],
+            theoretical_rewards: theoretical_rewards[id],
+        })
+    }
+
+    #[test]
+    fn test_bus_selection() {
+        let busses = busses([5, 9, 9, 1, 0, 2, 3, 4], [7, 3, 8, 2, 9, 2, 6, 5]);
+        assert_eq!(BusSelection::Id(6).select(), 6);
+        assert_eq!(BusSelection::LeastContention(busses).select(), 3);
+        assert_eq!(BusSelection::MostRewards(busses).select(), 1);
+    }
+
+    #[test]
+    fn test_mine_tx() {
+        let signer = Pubkey::new_unique();
+        let solution = Solution::new([1; 16], [2; 8]);
+        let busses = busses([0, 0, 0, 0, 7, 0, 0, 0], [0; BUS_COUNT]);
+
+        // A bare mine sets the limit and authenticates the ORE proof.
+        let ixs = MineTxBuilder::new(MINT_ADDRESS, signer, solution).build();
+        assert_eq!(ixs.len(), 3);
+        assert_eq!(ixs[0].program_id, COMPUTE_BUDGET_PROGRAM_ID);
+        assert_eq!(ixs[1].program_id, NOOP_PROGRAM_ID);
+        assert_eq!(ixs[2].data[0], OreInstruction::Mine as u8);
+
+        // Every option adds its instruction and compute budget.
+        let builder = MineTxBuilder::new(MINT_ADDRESS, signer, solution)
+            .bus(BusSelection::MostRewards(busses))
+            .priority_fee(1_000)
+            .mine_policy(MINE_POLICY_REQUIRE_AUTH)
+            .coal_boosts(&[Pubkey::new_unique()])
+            .reset(true)
+            .claim(signer, 100);
+        let ixs = builder.build();
+        let proof = proof_pda(MINT_ADDRESS, signer).0;
+        let programs: Vec<_> = ixs.iter().map(|ix| ix.program_id).collect();
+        assert_eq!(
+            programs,
+            vec![
+                COMPUTE_BUDGET_PROGRAM_ID,
+                COMPUTE_BUDGET_PROGRAM_ID,
+                NOOP_PROGRAM_ID,
+                NOOP_PROGRAM_ID,
+                crate::ID,
+                crate::ID,
+                crate::ID,
+            ]
+        );
+        assert_eq!(ixs[2].data, proof.to_bytes());
+        assert_eq!(ixs[3].data, ore_api::state::proof_pda(proof).0.to_bytes());
+        assert_eq!(ixs[4].data[0], OreInstruction::Reset as u8);
+        assert_eq!(ixs[5].data[0], OreInstruction::Mine as u8);
+        assert_eq!(ixs[6].data[0], OreInstruction::Claim as u8);
+        assert_eq!(ixs[5].accounts[2].pubkey, bus_pda(MINT_ADDRESS, 4).0);
+        let budget = MINE_COMPUTE_UNITS
+            + ORE_MINE_COMPUTE_UNITS
+            + COAL_BOOST_COMPUTE_UNITS
+            + RESET_COMPUTE_UNITS
+            + CLAIM_COMPUTE_UNITS
+            + ORE_CLAIM_COMPUTE_UNITS;
+        assert!(builder.compute_units() > budget + TX_OVERHEAD_COMPUTE_UNITS);
+        assert!(builder.compute_units() < budget * 2);
+        assert_eq!(ixs[0].data[1..5], builder.compute_units().to_le_bytes());
+        assert_eq!(builder.compute_unit_limit(1).build()[0].data[1..5], 1u32.to_le_bytes());
+    }
+}
@@ -0,0 +1,130 @@
+use clap::Args;
+use coal_api::consts::{METADATA_NAME, METADATA_SYMBOL, METADATA_URI, MINT_V1_ADDRESS};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Args, Debug)]
+pub struct InitializeArgs {
+    #[arg(long, value_name = "HEX", value_parser = parse_mint_noise, help = "16 bytes of noise to derive the mint address from [default: the COAL mint noise]")]
+    pub mint_noise: Option<[u8; 16]>,
+
+    #[arg(long, default_value = METADATA_NAME, help = "Token name, up to 32 bytes")]
+    pub name: String,
+
+    #[arg(long, default_value = METADATA_SYMBOL, help = "Token symbol, up to 8 bytes")]
+    pub symbol: String,
+
+    #[arg(long, default_value = METADATA_URI, help = "Token metadata URI, up to 128 bytes")]
+    pub uri: String,
+
+    #[arg(long, default_value_t = MINT_V1_ADDRESS, help = "Legacy mint that can be upgraded into this mint")]
+    pub migration_mint: Pubkey,
+
+    #[arg(long, default_value_t = 10_000, help = "Tokens minted per legacy token, in basis points")]
+    pub migration_ratio: u64,
+
+    #[arg(long, default_value_t = 0, help = "Last epoch upgrades are accepted, or 0 for no deadline")]
+    pub migration_deadline: u64,
+
+    #[arg(long, default_value_t = 21_000_000, help = "Maximum supply, in whole tokens")]
+    pub max_supply: u64,
+
+    #[arg(long, default_value_t = 17_280, help = "Epochs between emission decays, or 0 to never decay")]
+    pub schedule_epochs: u64,
+
+    #[arg(long, default_value_t = 2000, help = "Emission decay per schedule, in basis points")]
+    pub decay_basis_points: u64,
+
+    #[arg(long, default_value_t = Pubkey::default(), help = "Mint of the wrapped ingredient")]
+    pub wrapped_mint: Pubkey,
+
+    #[arg(long, default_value_t = 0.0, help = "Ratio of the wrapped ingredient")]
+    pub wrapped_ratio: f64,
+
+    #[arg(long, default_value_t = Pubkey::default(), help = "Mint of the burned ingredient")]
+    pub burned_mint: Pubkey,
+
+    #[arg(long, default_value_t = 0.0, help = "Ratio of the burned ingredient")]
+    pub burned_ratio: f64,
+
+    #[arg(long, help = "Let burned tokens be mined again instead of counting towards the max supply")]
+    pub recycle_burns: bool,
+
+    #[arg(long, default_value_t = 0.0, help = "Tokens minted to the first resetter of each epoch")]
+    pub crank_reward: f64,
+
+    #[arg(long, help = "Create the mint under Token-2022")]
+    pub token_2022: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct OpenArgs {
+    #[arg(long, help = "Keypair allowed to mine with the proof [default: the signer]")]
+    pub miner: Option<Pubkey>,
+}
+
+#[derive(Args, Debug)]
+pub struct MineArgs {
+    #[arg(long, default_value_t = 1, help = "Number of CPU cores to hash with")]
+    pub cores: u64,
+
+    #[arg(long, default_value_t = 55, help = "Seconds to search for the best hash before submitting")]
+    pub seconds: u64,
+
+    #[arg(long = "boost", value_name = "MINT", help = "Mint of a COAL boost the signer has staked in, up to 3 times")]
+    pub boosts: Vec<Pubkey>,
+}
+
+#[derive(Args, Debug)]
+pub struct ClaimArgs {
+    #[arg(long, help = "Amount to claim, in whole tokens [default: the full balance]")]
+    pub amount: Option<f64>,
+
+    #[arg(long, help = "Token account to claim to [default: the signer's associated token account]")]
+    pub beneficiary: Option<Pubkey>,
+}
+
+#[derive(Args, Debug)]
+pub struct CloseArgs {}
+
+#[derive(Args, Debug)]
+pub struct UpdateMinerArgs {
+    #[arg(help = "Keypair allowed to mine with the proof")]
+    pub miner: Pubkey,
+}
+
+#[derive(Args, Debug)]
+pub struct ResetArgs {
+    #[arg(long, help = "Token account to receive the crank reward, if the mint pays one")]
+    pub beneficiary: Option<Pubkey>,
+}
+
+#[derive(Args, Debug)]
+pub struct StatusArgs {
+    #[arg(long, help = "Authority of the proof to print [default: the signer]")]
+    pub authority: Option<Pubkey>,
+}
+
+fn parse_mint_noise(value: &str) -> Result<[u8; 16], String> {
+    let value = value.trim_start_matches("0x");
+    if value.len() != 32 || !value.is_ascii() {
+        return Err("expected 32 hex characters".to_string());
+    }
+    let mut noise = [0; 16];
+    for (i, byte) in noise.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[i * 2..i * 2 + 2], 16).map_err(|err| err.to_string())?;
+    }
+    Ok(noise)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::parse_mint_noise;
+
+    #[test]
+    fn test_parse_mint_noise() {
+        let noise = parse_mint_noise("0x000102030405060708090a0b0c0d0eff").unwrap();
+        assert_eq!(noise, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 255]);
+        assert!(parse_mint_noise("0102").is_err());
+        assert!(parse_mint_noise("zz0102030405060708090a0b0c0d0e0f").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use coal_api::consts::TOKEN_DECIMALS;
+use solana_sdk::signature::Signer;
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{args::ClaimArgs, Coal, Result};
+
+impl Coal {
+    pub async fn claim(&self, args: ClaimArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let token_program_id = self.token_program().await?;
+        let proof = self.client.get_proof(self.mint, signer).await?;
+        let amount = args
+            .amount
+            .map(|amount| spl_token::ui_amount_to_amount(amount, TOKEN_DECIMALS))
+            .unwrap_or(proof.balance);
+
+        // Claim to the signer's token account by default, creating it if needed.
+        let mut ixs = vec![];
+        let beneficiary = match args.beneficiary {
+            Some(beneficiary) => beneficiary,
+            None => {
+                ixs.push(create_associated_token_account_idempotent(
+                    &signer,
+                    &signer,
+                    &self.mint,
+                    &token_program_id,
+                ));
+                get_associated_token_address_with_program_id(&signer, &self.mint, &token_program_id)
+            }
+        };
+        ixs.push(coal_api::sdk::claim_with_program_id(
+            self.mint,
+            signer,
+            beneficiary,
+            amount,
+            &token_program_id,
+        ));
+        self.send_and_confirm(&ixs, None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use solana_sdk::signature::Signer;
+
+use crate::{args::CloseArgs, Coal, Result};
+
+impl Coal {
+    pub async fn close(&self, _args: CloseArgs) -> Result<()> {
+        let ix = coal_api::sdk::close(self.mint, self.signer.pubkey());
+        self.send_and_confirm(&[ix], None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use coal_api::{
+    consts::{MINT_NOISE, ONE_ORE, TOKEN_DECIMALS},
+    instruction::Initialize,
+    sdk::to_padded_bytes,
+};
+use solana_sdk::signature::Signer;
+
+use crate::{args::InitializeArgs, Coal, Result};
+
+impl Coal {
+    pub async fn initialize(&self, args: InitializeArgs) -> Result<()> {
+        let token_program_id = if args.token_2022 {
+            spl_token_2022::ID
+        } else {
+            spl_token::ID
+        };
+        let ix = coal_api::sdk::initialize_with_args(
+            self.signer.pubkey(),
+            &token_program_id,
+            initialize_args(&args),
+        );
+        self.send_and_confirm(&[ix], Some(400_000)).await?;
+        Ok(())
+    }
+}
+
+/// Maps the command line flags to the initialize instruction args.
+///
+/// The bumps are left empty, since the instruction builder derives them.
+fn initialize_args(args: &InitializeArgs) -> Initialize {
+    Initialize {
+        treasury_bump: 0,
+        mint_bump: 0,
+        mint_noise: args.mint_noise.unwrap_or(MINT_NOISE),
+        metadata_name: to_padded_bytes(&args.name),
+        metadata_symbol: to_padded_bytes(&args.symbol),
+        metadata_uri: to_padded_bytes(&args.uri),
+        migration_mint: args.migration_mint,
+        max_supply: args.max_supply.saturating_mul(ONE_ORE).to_le_bytes(),
+        schedule_epochs: args.schedule_epochs.to_le_bytes(),
+        decay_basis_points: args.decay_basis_points.to_le_bytes(),
+        wrapped_ingredient_mint: args.wrapped_mint,
+        wrapped_ingredient_ratio: args.wrapped_ratio.to_le_bytes(),
+        burned_ingredient_mint: args.burned_mint,
+        burned_ingredient_ratio: args.burned_ratio.to_le_bytes(),
+        recycle_burns: (args.recycle_burns as u64).to_le_bytes(),
+        migration_ratio: args.migration_ratio.to_le_bytes(),
+        migration_deadline: args.migration_deadline.to_le_bytes(),
+        crank_reward: spl_token::ui_amount_to_amount(args.crank_reward, TOKEN_DECIMALS).to_le_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use coal_api::consts::{MINT_NOISE, MINT_V1_ADDRESS, ONE_ORE};
+
+    use crate::{initialize::initialize_args, Args, Commands};
+
+    fn parse(flags: &[&str]) -> coal_api::instruction::Initialize {
+        let args = Args::parse_from([&["coal", "initialize"], flags].concat());
+        let Commands::Initialize(args) = args.command else {
+            panic!("expected the initialize command");
+        };
+        initialize_args(&args)
+    }
+
+    #[test]
+    fn test_initialize_args_defaults() {
+        let args = parse(&[]);
+        assert_eq!(args.mint_noise, MINT_NOISE);
+        assert_eq!(&args.metadata_name[..4], b"ORE\0");
+        assert_eq!(args.migration_mint, MINT_V1_ADDRESS);
+        assert_eq!(u64::from_le_bytes(args.max_supply), ONE_ORE * 21_000_000);
+        assert_eq!(u64::from_le_bytes(args.schedule_epochs), 17_280);
+        assert_eq!(u64::from_le_bytes(args.decay_basis_points), 2000);
+        assert_eq!(u64::from_le_bytes(args.recycle_burns), 0);
+        assert_eq!(u64::from_le_bytes(args.migration_ratio), 10_000);
+        assert_eq!(u64::from_le_bytes(args.crank_reward), 0);
+    }
+
+    #[test]
+    fn test_initialize_args_flags() {
+        let args = parse(&[
+            "--mint-noise",
+            "000102030405060708090a0b0c0d0e0f",
+            "--symbol",
+            "COAL",
+            "--max-supply",
+            "1000",
+            "--wrapped-ratio",
+            "0.5",
+            "--recycle-burns",
+            "--migration-deadline",
+            "42",
+            "--crank-reward",
+            "0.01",
+        ]);
+        assert_eq!(args.mint_noise, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
+        assert_eq!(&args.metadata_symbol, b"COAL\0\0\0\0");
+        assert_eq!(u64::from_le_bytes(args.max_supply), ONE_ORE * 1000);
+        assert_eq!(f64::from_le_bytes(args.wrapped_ingredient_ratio), 0.5);
+        assert_eq!(u64::from_le_bytes(args.recycle_burns), 1);
+        assert_eq!(u64::from_le_bytes(args.migration_deadline), 42);
+        assert_eq!(u64::from_le_bytes(args.crank_reward), ONE_ORE / 100);
+    }
+}
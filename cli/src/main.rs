@@ -0,0 +1,119 @@
+mod args;
+mod claim;
+mod close;
+mod initialize;
+mod mine;
+mod open;
+mod reset;
+mod send;
+mod status;
+mod update_miner;
+
+use args::*;
+use clap::{Parser, Subcommand};
+use coal_api::{client::CoalClient, consts::MINT_ADDRESS};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Shared state for every command.
+pub struct Coal {
+    pub client: CoalClient,
+    pub signer: Keypair,
+    pub mint: Pubkey,
+    pub priority_fee: u64,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "coal", about = "Operate and mine COAL", version)]
+struct Args {
+    #[arg(long, global = true, default_value = "http://127.0.0.1:8899", help = "RPC URL of the cluster")]
+    rpc: String,
+
+    #[arg(long, global = true, value_name = "PATH", help = "Keypair to sign with [default: ~/.config/solana/id.json]")]
+    keypair: Option<String>,
+
+    #[arg(long, global = true, default_value_t = MINT_ADDRESS, help = "Mint to operate on")]
+    mint: Pubkey,
+
+    #[arg(long, global = true, value_name = "MICROLAMPORTS", default_value_t = 0, help = "Price per compute unit")]
+    priority_fee: u64,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    #[command(about = "Initialize a mint and its global accounts")]
+    Initialize(InitializeArgs),
+
+    #[command(about = "Open a proof account to mine with")]
+    Open(OpenArgs),
+
+    #[command(about = "Mine with the CPU")]
+    Mine(MineArgs),
+
+    #[command(about = "Claim mining rewards")]
+    Claim(ClaimArgs),
+
+    #[command(about = "Close the proof account and reclaim its rent")]
+    Close(CloseArgs),
+
+    #[command(about = "Change the keypair allowed to mine with the proof")]
+    UpdateMiner(UpdateMinerArgs),
+
+    #[command(about = "Reset the epoch, if it has ended")]
+    Reset(ResetArgs),
+
+    #[command(about = "Print the config, busses and proof of the mint")]
+    Status(StatusArgs),
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let keypair = args.keypair.unwrap_or_else(default_keypair_path);
+    let signer = read_keypair_file(&keypair)
+        .map_err(|err| format!("Failed to read keypair {}: {}", keypair, err))?;
+    let rpc = RpcClient::new_with_commitment(args.rpc, CommitmentConfig::confirmed());
+    let coal = Coal {
+        client: CoalClient::new(rpc),
+        signer,
+        mint: args.mint,
+        priority_fee: args.priority_fee,
+    };
+
+    match args.command {
+        Commands::Initialize(args) => coal.initialize(args).await,
+        Commands::Open(args) => coal.open(args).await,
+        Commands::Mine(args) => coal.mine(args).await,
+        Commands::Claim(args) => coal.claim(args).await,
+        Commands::Close(args) => coal.close(args).await,
+        Commands::UpdateMiner(args) => coal.update_miner(args).await,
+        Commands::Reset(args) => coal.reset(args).await,
+        Commands::Status(args) => coal.status(args).await,
+    }
+}
+
+fn default_keypair_path() -> String {
+    let home = std::env::var("HOME").unwrap_or_default();
+    format!("{}/.config/solana/id.json", home)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use crate::Args;
+
+    #[test]
+    fn test_args() {
+        Args::command().debug_assert();
+    }
+}
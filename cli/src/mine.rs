@@ -0,0 +1,148 @@
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use coal_api::{
+    consts::TOKEN_DECIMALS,
+    sdk::{next_reset_at, preview_mine},
+    state::{boost_pda, proof_pda, stake_pda, Boost, Stake},
+    tx::{BusSelection, MineTxBuilder},
+};
+use drillx::{equix::SolverMemory, Solution};
+use ore_api::consts::CONFIG_ADDRESS as ORE_CONFIG_ADDRESS;
+use solana_sdk::signature::Signer;
+
+use crate::{args::MineArgs, Coal, Result};
+
+impl Coal {
+    /// Mines in a loop, submitting the best hash found in each round to the bus with the most rewards left.
+    pub async fn mine(&self, args: MineArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let proof_address = proof_pda(self.mint, signer).0;
+        let ore_proof_address = ore_api::state::proof_pda(proof_address).0;
+        let token_program_id = self.token_program().await?;
+
+        loop {
+            // Hashes must satisfy both the COAL and the ORE min difficulty.
+            let config = self.client.get_config(self.mint).await?;
+            let proof = self.client.get_proof(self.mint, signer).await?;
+            let ore_config = self
+                .client
+                .get_state::<ore_api::state::Config>(ORE_CONFIG_ADDRESS)
+                .await?;
+            let ore_proof = self
+                .client
+                .get_state::<ore_api::state::Proof>(ore_proof_address)
+                .await?;
+            let min_difficulty = config.min_difficulty.max(ore_config.min_difficulty) as u32;
+            println!(
+                "Mining for {}s at min difficulty {} on {} cores",
+                args.seconds, min_difficulty, args.cores
+            );
+            let (cores, seconds, challenge) = (args.cores, args.seconds, ore_proof.challenge);
+            let solution = tokio::task::spawn_blocking(move || {
+                find_hash(challenge, min_difficulty, cores, seconds)
+            })
+            .await?;
+
+            // Submit to the bus with the most rewards left.
+            let busses = self.client.get_busses(self.mint).await?;
+            let bus = BusSelection::MostRewards(busses);
+            let mut boosts = vec![];
+            for mint in &args.boosts {
+                let boost_address = boost_pda(*mint).0;
+                let boost = self.client.get_state::<Boost>(boost_address).await?;
+                let stake = self
+                    .client
+                    .get_state::<Stake>(stake_pda(boost_address, signer).0)
+                    .await?;
+                boosts.push((boost, stake));
+            }
+            let event = preview_mine(
+                &config,
+                &busses[bus.select() as usize],
+                &proof,
+                &solution,
+                &boosts,
+                unix_timestamp(),
+            )
+            .map_err(|_| "The hash is below the min difficulty")?;
+            println!(
+                "Difficulty {} Reward {}",
+                event.difficulty,
+                spl_token::amount_to_ui_amount(event.reward, TOKEN_DECIMALS)
+            );
+
+            // Reset the epoch in the same transaction if it has ended.
+            let ixs = MineTxBuilder::new(self.mint, signer, solution)
+                .bus(bus)
+                .priority_fee(self.priority_fee)
+                .mine_policy(config.mine_policy)
+                .coal_boosts(&args.boosts)
+                .reset(next_reset_at(&config) <= unix_timestamp())
+                .token_program_id(token_program_id)
+                .build();
+            if let Err(err) = self.send_and_confirm_ixs(&ixs).await {
+                println!("Failed to submit hash: {}", err);
+            }
+        }
+    }
+}
+
+/// Searches the challenge for the hash with the highest difficulty, splitting the nonce space
+/// across threads. Returns once the time is up and a hash meets the min difficulty.
+fn find_hash(challenge: [u8; 32], min_difficulty: u32, cores: u64, seconds: u64) -> Solution {
+    let cores = cores.max(1);
+    let handles: Vec<_> = (0..cores)
+        .map(|i| {
+            std::thread::spawn(move || {
+                let timer = Instant::now();
+                let mut memory = SolverMemory::new();
+                let mut nonce = u64::MAX.saturating_div(cores).saturating_mul(i);
+                let mut best_difficulty = 0;
+                let mut best_solution = Solution::new([0; 16], [0; 8]);
+                loop {
+                    let nonce_bytes = nonce.to_le_bytes();
+                    for hash in drillx::hashes_with_memory(&mut memory, &challenge, &nonce_bytes) {
+                        let difficulty = hash.difficulty();
+                        if difficulty.gt(&best_difficulty) {
+                            best_difficulty = difficulty;
+                            best_solution = Solution::new(hash.d, nonce_bytes);
+                        }
+                    }
+                    if nonce % 16 == 0
+                        && timer.elapsed().as_secs().ge(&seconds)
+                        && best_difficulty.ge(&min_difficulty)
+                    {
+                        return (best_difficulty, best_solution);
+                    }
+                    nonce = nonce.wrapping_add(1);
+                }
+            })
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .max_by_key(|(difficulty, _)| *difficulty)
+        .map(|(_, solution)| solution)
+        .unwrap()
+}
+
+fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mine::find_hash;
+
+    #[test]
+    fn test_find_hash() {
+        let challenge = [3; 32];
+        let solution = find_hash(challenge, 4, 1, 0);
+        assert!(solution.is_valid(&challenge));
+        assert!(solution.to_hash().difficulty().ge(&4));
+    }
+}
@@ -0,0 +1,13 @@
+use solana_sdk::signature::Signer;
+
+use crate::{args::OpenArgs, Coal, Result};
+
+impl Coal {
+    pub async fn open(&self, args: OpenArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let miner = args.miner.unwrap_or(signer);
+        let ix = coal_api::sdk::open(self.mint, signer, miner, signer);
+        self.send_and_confirm(&[ix], None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use coal_api::instruction::Reset;
+use solana_sdk::signature::Signer;
+
+use crate::{args::ResetArgs, Coal, Result};
+
+impl Coal {
+    /// Sends a reset. Fails with `ResetTooEarly` if the epoch has not ended.
+    pub async fn reset(&self, args: ResetArgs) -> Result<()> {
+        let token_program_id = self.token_program().await?;
+        let ix = coal_api::sdk::reset_with_args(
+            self.mint,
+            self.signer.pubkey(),
+            &token_program_id,
+            Reset { strict: 1 },
+            args.beneficiary,
+        );
+        self.send_and_confirm(&[ix], None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+
+use crate::{Coal, Result};
+
+impl Coal {
+    /// Signs, sends and confirms a transaction with the given instructions.
+    ///
+    /// A compute unit price is prepended when a priority fee is set.
+    pub async fn send_and_confirm(
+        &self,
+        ixs: &[Instruction],
+        compute_unit_limit: Option<u32>,
+    ) -> Result<Signature> {
+        let mut final_ixs = vec![];
+        if let Some(limit) = compute_unit_limit {
+            final_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        if self.priority_fee > 0 {
+            final_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(self.priority_fee));
+        }
+        final_ixs.extend_from_slice(ixs);
+        self.send_and_confirm_ixs(&final_ixs).await
+    }
+
+    /// Signs, sends and confirms a transaction with exactly the given instructions, for lists
+    /// that already set their compute budget.
+    pub async fn send_and_confirm_ixs(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let rpc = self.client.rpc();
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.signer.pubkey()),
+            &[&self.signer],
+            blockhash,
+        );
+        let signature = rpc.send_and_confirm_transaction(&tx).await?;
+        println!("OK {}", signature);
+        Ok(signature)
+    }
+
+    /// Returns the token program that owns the mint.
+    pub async fn token_program(&self) -> Result<Pubkey> {
+        Ok(self.client.rpc().get_account(&self.mint).await?.owner)
+    }
+}
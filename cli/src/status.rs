@@ -0,0 +1,56 @@
+use coal_api::{
+    client::CoalClientError,
+    consts::TOKEN_DECIMALS,
+    sdk::next_reset_at,
+};
+use solana_sdk::signature::Signer;
+
+use crate::{args::StatusArgs, Coal, Result};
+
+impl Coal {
+    pub async fn status(&self, args: StatusArgs) -> Result<()> {
+        let config = self.client.get_config(self.mint).await?;
+        println!("Mint               {}", self.mint);
+        println!("Epoch              {}", config.current_epoch);
+        println!("Next reset at      {}", next_reset_at(&config));
+        println!("Base reward rate   {}", ui_amount(config.base_reward_rate));
+        println!("Min difficulty     {}", config.min_difficulty);
+        println!("Epoch rewards      {}", ui_amount(config.get_epoch_rewards()));
+        println!("Max supply         {}", ui_amount(config.max_supply));
+        println!("Crank reward       {}", ui_amount(config.crank_reward));
+        println!("Total rewards      {}", ui_amount(config.total_rewards));
+        println!("Total burned       {}", ui_amount(config.total_burned));
+        println!("ORE backing        {}", ui_amount(config.ore_balance));
+
+        println!();
+        for bus in self.client.get_busses(self.mint).await? {
+            println!(
+                "Bus {:<15}{} remaining, {} theoretical",
+                bus.id,
+                ui_amount(bus.rewards),
+                ui_amount(bus.theoretical_rewards)
+            );
+        }
+
+        println!();
+        let authority = args.authority.unwrap_or(self.signer.pubkey());
+        match self.client.get_proof(self.mint, authority).await {
+            Ok(proof) => {
+                println!("Authority          {}", proof.authority);
+                println!("Miner              {}", proof.miner);
+                println!("Balance            {}", ui_amount(proof.balance));
+                println!("Total hashes       {}", proof.total_hashes);
+                println!("Total rewards      {}", ui_amount(proof.total_rewards));
+            }
+            Err(CoalClientError::AccountNotFound(_)) => {
+                println!("No proof for {}", authority);
+            }
+            Err(err) => return Err(err.into()),
+        }
+        Ok(())
+    }
+}
+
+fn ui_amount(amount: u64) -> f64 {
+    spl_token::amount_to_ui_amount(amount, TOKEN_DECIMALS)
+}
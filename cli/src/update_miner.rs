@@ -0,0 +1,11 @@
+use solana_sdk::signature::Signer;
+
+use crate::{args::UpdateMinerArgs, Coal, Result};
+
+impl Coal {
+    pub async fn update_miner(&self, args: UpdateMinerArgs) -> Result<()> {
+        let ix = coal_api::sdk::update(self.mint, self.signer.pubkey(), args.miner);
+        self.send_and_confirm(&[ix], None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use std::future::Future;
+
+use coal_api::{client::CoalClient, state::Config};
+use solana_sdk::{
+    account::from_account,
+    clock::Clock,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+use crate::Result;
+
+/// The cluster the crank reads state from and sends resets to.
+pub trait Cluster {
+    /// The account that signs and pays for resets.
+    fn payer(&self) -> Pubkey;
+
+    /// Fetches the cluster clock.
+    fn clock(&self) -> impl Future<Output = Result<Clock>> + Send;
+
+    /// Fetches the config of the mint.
+    fn config(&self, mint: Pubkey) -> impl Future<Output = Result<Config>> + Send;
+
+    /// Fetches the token program that owns the mint.
+    fn token_program(&self, mint: Pubkey) -> impl Future<Output = Result<Pubkey>> + Send;
+
+    /// Signs and sends a transaction, returning once it is confirmed.
+    fn send(&self, ixs: &[Instruction]) -> impl Future<Output = Result<Signature>> + Send;
+}
+
+/// A cluster reached over RPC.
+pub struct RpcCluster {
+    client: CoalClient,
+    payer: Keypair,
+}
+
+impl RpcCluster {
+    pub fn new(client: CoalClient, payer: Keypair) -> Self {
+        Self { client, payer }
+    }
+}
+
+impl Cluster for RpcCluster {
+    fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    async fn clock(&self) -> Result<Clock> {
+        let account = self.client.rpc().get_account(&sysvar::clock::ID).await?;
+        from_account::<Clock, _>(&account).ok_or_else(|| "Invalid clock sysvar".into())
+    }
+
+    async fn config(&self, mint: Pubkey) -> Result<Config> {
+        Ok(self.client.get_config(mint).await?)
+    }
+
+    async fn token_program(&self, mint: Pubkey) -> Result<Pubkey> {
+        Ok(self.client.rpc().get_account(&mint).await?.owner)
+    }
+
+    async fn send(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let rpc = self.client.rpc();
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        Ok(rpc.send_and_confirm_transaction(&tx).await?)
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    future::Future,
+    sync::{atomic::Ordering, Arc},
+    time::Duration,
+};
+
+use coal_api::{
+    consts::{EPOCH_DURATION, MINT_ADDRESS},
+    instruction::Reset,
+    sdk::next_reset_at,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, pubkey::Pubkey,
+    signature::Signature,
+};
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{Cluster, Metrics, Result};
+
+/// Settings for the crank.
+#[derive(Clone, Debug)]
+pub struct CrankConfig {
+    /// Mints to reset.
+    pub mints: Vec<Pubkey>,
+
+    /// Price per compute unit of each reset, in microlamports.
+    pub priority_fee: u64,
+
+    /// Times a failed reset is resent before giving up until the next tick.
+    pub max_retries: u32,
+
+    /// Delay between resends, and before the next tick after an error.
+    pub retry_delay: Duration,
+
+    /// Longest the crank sleeps between ticks, so it catches up with clock drift.
+    pub max_sleep: Duration,
+
+    /// Whether to collect the crank reward of mints that pay one, into the payer's associated
+    /// token account.
+    pub collect_rewards: bool,
+}
+
+impl Default for CrankConfig {
+    fn default() -> Self {
+        Self {
+            mints: vec![MINT_ADDRESS],
+            priority_fee: 0,
+            max_retries: 5,
+            retry_delay: Duration::from_millis(500),
+            max_sleep: Duration::from_secs(30),
+            collect_rewards: false,
+        }
+    }
+}
+
+/// What a tick did for a mint.
+#[derive(Debug, PartialEq)]
+pub enum MintStatus {
+    /// The epoch has not ended yet.
+    Waiting,
+
+    /// The crank reset the mint to a new epoch.
+    Reset { epoch: u64, signature: Signature },
+
+    /// Someone else reset the epoch first.
+    Skipped,
+}
+
+/// The outcome of a tick.
+#[derive(Debug)]
+pub struct Tick {
+    /// How long to wait before the next tick.
+    pub wait: Duration,
+
+    /// The status of each configured mint, or the error that kept it from being reset.
+    pub mints: Vec<(Pubkey, Result<MintStatus>)>,
+}
+
+/// Resets the configured mints at their epoch boundaries.
+pub struct Crank<C> {
+    cluster: C,
+    config: CrankConfig,
+    metrics: Arc<Metrics>,
+}
+
+impl<C: Cluster> Crank<C> {
+    pub fn new(cluster: C, config: CrankConfig) -> Self {
+        Self {
+            cluster,
+            config,
+            metrics: Arc::new(Metrics::default()),
+        }
+    }
+
+    pub fn cluster(&self) -> &C {
+        &self.cluster
+    }
+
+    pub fn metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    /// Runs forever, sleeping until the next epoch boundary between ticks. Each tick, or the
+    /// error reading the cluster clock, is passed to `report`.
+    pub async fn run(&self, mut report: impl FnMut(&Result<Tick>)) -> ! {
+        loop {
+            let tick = self.tick().await;
+            report(&tick);
+            let wait = tick.map_or(self.config.retry_delay, |tick| tick.wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Resets every mint whose epoch has ended by the cluster clock. Fails only if the clock
+    /// cannot be read; the errors of each mint are returned with its status.
+    pub async fn tick(&self) -> Result<Tick> {
+        let clock = self.fetch(self.cluster.clock()).await?;
+        let mut wait = self.config.max_sleep;
+        let mut mints = Vec::with_capacity(self.config.mints.len());
+        for mint in self.config.mints.iter() {
+            let status = match self.tick_mint(*mint, clock.unix_timestamp).await {
+                Ok((mint_wait, status)) => {
+                    wait = wait.min(mint_wait);
+                    Ok(status)
+                }
+                Err(err) => {
+                    wait = wait.min(self.config.retry_delay);
+                    Err(err)
+                }
+            };
+            mints.push((*mint, status));
+        }
+        Ok(Tick { wait, mints })
+    }
+
+    async fn tick_mint(&self, mint: Pubkey, now: i64) -> Result<(Duration, MintStatus)> {
+        let config = self.fetch(self.cluster.config(mint)).await?;
+        let reset_at = next_reset_at(&config);
+        if reset_at.gt(&now) {
+            let wait = Duration::from_secs(reset_at.saturating_sub(now) as u64);
+            return Ok((wait, MintStatus::Waiting));
+        }
+        let epoch_wait = Duration::from_secs(EPOCH_DURATION as u64);
+        let collect_reward = self.config.collect_rewards && config.crank_reward.gt(&0);
+        let Some(signature) = self.reset(mint, reset_at, collect_reward).await? else {
+            self.metrics.resets_skipped.fetch_add(1, Ordering::Relaxed);
+            return Ok((epoch_wait, MintStatus::Skipped));
+        };
+
+        self.metrics.resets.fetch_add(1, Ordering::Relaxed);
+        let config = self.fetch(self.cluster.config(mint)).await?;
+        self.metrics
+            .last_reset_delay
+            .store(config.last_reset_at.saturating_sub(reset_at), Ordering::Relaxed);
+        let status = MintStatus::Reset {
+            epoch: config.current_epoch,
+            signature,
+        };
+        Ok((epoch_wait, status))
+    }
+
+    /// Sends a strict reset, retrying failed sends. Returns `None` if the epoch was reset by
+    /// someone else in the meantime.
+    async fn reset(
+        &self,
+        mint: Pubkey,
+        reset_at: i64,
+        collect_reward: bool,
+    ) -> Result<Option<Signature>> {
+        let token_program_id = self.fetch(self.cluster.token_program(mint)).await?;
+        let ixs = reset_instructions(
+            mint,
+            self.cluster.payer(),
+            &token_program_id,
+            self.config.priority_fee,
+            collect_reward,
+        );
+        let mut attempts = 0;
+        loop {
+            match self.cluster.send(&ixs).await {
+                Ok(signature) => return Ok(Some(signature)),
+                Err(err) => {
+                    self.metrics.send_failures.fetch_add(1, Ordering::Relaxed);
+
+                    // A strict reset fails with `ResetTooEarly` once another reset has landed.
+                    let config = self.fetch(self.cluster.config(mint)).await?;
+                    if next_reset_at(&config).gt(&reset_at) {
+                        return Ok(None);
+                    }
+                    if attempts.ge(&self.config.max_retries) {
+                        return Err(err);
+                    }
+                    attempts += 1;
+                    tokio::time::sleep(self.config.retry_delay).await;
+                }
+            }
+        }
+    }
+
+    async fn fetch<T>(&self, future: impl Future<Output = Result<T>>) -> Result<T> {
+        future.await.inspect_err(|_| {
+            self.metrics.fetch_failures.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+}
+
+/// Builds the instructions of a strict reset transaction, with a compute unit price if a
+/// priority fee is set. If the crank reward is collected, the payer's associated token account is
+/// created first if needed.
+pub fn reset_instructions(
+    mint: Pubkey,
+    payer: Pubkey,
+    token_program_id: &Pubkey,
+    priority_fee: u64,
+    collect_reward: bool,
+) -> Vec<Instruction> {
+    let mut ixs = vec![];
+    if priority_fee.gt(&0) {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(priority_fee));
+    }
+    let mut crank_reward_tokens = None;
+    if collect_reward {
+        ixs.push(create_associated_token_account_idempotent(
+            &payer,
+            &payer,
+            &mint,
+            token_program_id,
+        ));
+        crank_reward_tokens = Some(get_associated_token_address_with_program_id(
+            &payer,
+            &mint,
+            token_program_id,
+        ));
+    }
+    ixs.push(coal_api::sdk::reset_with_args(
+        mint,
+        payer,
+        token_program_id,
+        Reset { strict: 1 },
+        crank_reward_tokens,
+    ));
+    ixs
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::consts::MINT_ADDRESS;
+    use solana_sdk::{compute_budget, pubkey::Pubkey};
+
+    use crate::reset_instructions;
+
+    #[test]
+    fn test_reset_instructions() {
+        let payer = Pubkey::new_unique();
+        let token_program_id = Pubkey::new_unique();
+        let ixs = reset_instructions(MINT_ADDRESS, payer, &token_program_id, 0, false);
+        assert_eq!(ixs.len(), 1);
+        assert_eq!(ixs[0].program_id, coal_api::ID);
+        assert_eq!(ixs[0].accounts.len(), 14);
+
+        let ixs = reset_instructions(MINT_ADDRESS, payer, &token_program_id, 1_000, true);
+        assert_eq!(ixs.len(), 3);
+        assert_eq!(ixs[0].program_id, compute_budget::ID);
+        assert_eq!(ixs[1].program_id, spl_associated_token_account::ID);
+        assert_eq!(ixs[2].program_id, coal_api::ID);
+        assert_eq!(ixs[2].accounts.len(), 15);
+    }
+}
@@ -0,0 +1,16 @@
+//! Crank that resets COAL epochs as soon as they end.
+//!
+//! Mining fails with `NeedsReset` until someone resets the epoch, and resets sent early are
+//! silently ignored. The crank reads the cluster clock, waits for the epoch boundary of each
+//! configured mint, and submits the reset with retries and a priority fee.
+
+mod cluster;
+mod crank;
+mod metrics;
+
+pub use cluster::*;
+pub use crank::*;
+pub use metrics::*;
+
+/// Errors are boxed, since they come from the RPC client, the program and the clock alike.
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
@@ -0,0 +1,114 @@
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use clap::Parser;
+use coal_api::{client::CoalClient, consts::MINT_ADDRESS};
+use coal_crank::{Crank, CrankConfig, Metrics, MintStatus, Result, RpcCluster, Tick};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::read_keypair_file};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "coal-crank", about = "Reset COAL epochs as soon as they end", version)]
+struct Args {
+    #[arg(long, default_value = "http://127.0.0.1:8899", help = "RPC URL of the cluster")]
+    rpc: String,
+
+    #[arg(long, value_name = "PATH", help = "Keypair that pays for resets [default: ~/.config/solana/id.json]")]
+    keypair: Option<String>,
+
+    #[arg(long = "mint", default_values_t = [MINT_ADDRESS], help = "Mint to reset, repeat for several mints")]
+    mints: Vec<Pubkey>,
+
+    #[arg(long, value_name = "MICROLAMPORTS", default_value_t = 0, help = "Price per compute unit")]
+    priority_fee: u64,
+
+    #[arg(long, default_value_t = 5, help = "Times a failed reset is resent")]
+    max_retries: u32,
+
+    #[arg(long, default_value_t = 500, help = "Milliseconds between resends")]
+    retry_delay_ms: u64,
+
+    #[arg(long, help = "Collect crank rewards into the payer's associated token accounts")]
+    collect_rewards: bool,
+
+    #[arg(long, value_name = "ADDRESS", help = "Address to serve Prometheus metrics on, like 0.0.0.0:9100")]
+    metrics_addr: Option<SocketAddr>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let keypair = args.keypair.unwrap_or_else(|| {
+        format!("{}/.config/solana/id.json", std::env::var("HOME").unwrap_or_default())
+    });
+    let payer = read_keypair_file(&keypair)
+        .map_err(|err| format!("Failed to read keypair {}: {}", keypair, err))?;
+    let rpc = RpcClient::new_with_commitment(args.rpc, CommitmentConfig::confirmed());
+    let crank = Crank::new(
+        RpcCluster::new(CoalClient::new(rpc), payer),
+        CrankConfig {
+            mints: args.mints,
+            priority_fee: args.priority_fee,
+            max_retries: args.max_retries,
+            retry_delay: Duration::from_millis(args.retry_delay_ms),
+            collect_rewards: args.collect_rewards,
+            ..Default::default()
+        },
+    );
+
+    if let Some(addr) = args.metrics_addr {
+        let listener = TcpListener::bind(addr).await?;
+        tokio::spawn(serve_metrics(listener, crank.metrics()));
+    }
+    crank.run(report).await
+}
+
+/// Prints the resets of a tick, and its errors.
+fn report(tick: &Result<Tick>) {
+    let tick = match tick {
+        Ok(tick) => tick,
+        Err(err) => return eprintln!("Failed to read the cluster clock: {}", err),
+    };
+    for (mint, status) in tick.mints.iter() {
+        match status {
+            Ok(MintStatus::Reset { epoch, signature }) => {
+                println!("Reset {} to epoch {}: {}", mint, epoch, signature)
+            }
+            Ok(MintStatus::Waiting | MintStatus::Skipped) => {}
+            Err(err) => eprintln!("Failed to reset {}: {}", mint, err),
+        }
+    }
+}
+
+/// Answers every connection with the metrics, whatever the request path.
+async fn serve_metrics(listener: TcpListener, metrics: Arc<Metrics>) {
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request).await;
+        let body = metrics.render();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use crate::Args;
+
+    #[test]
+    fn test_args() {
+        Args::command().debug_assert();
+    }
+}
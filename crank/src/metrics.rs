@@ -0,0 +1,75 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicI64, AtomicU64, Ordering},
+};
+
+/// Counters describing the crank's activity, rendered in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Resets that landed and advanced the epoch.
+    pub resets: AtomicU64,
+
+    /// Resets that were not needed, because someone else reset the epoch first.
+    pub resets_skipped: AtomicU64,
+
+    /// Attempts to send a reset that failed.
+    pub send_failures: AtomicU64,
+
+    /// Errors reading state from the cluster.
+    pub fetch_failures: AtomicU64,
+
+    /// Seconds between the epoch boundary and the cluster time of the last reset.
+    pub last_reset_delay: AtomicI64,
+}
+
+impl Metrics {
+    /// Renders the counters in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let counters = [
+            ("coal_crank_resets_total", "counter", self.resets.load(Ordering::Relaxed) as i64),
+            (
+                "coal_crank_resets_skipped_total",
+                "counter",
+                self.resets_skipped.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "coal_crank_send_failures_total",
+                "counter",
+                self.send_failures.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "coal_crank_fetch_failures_total",
+                "counter",
+                self.fetch_failures.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "coal_crank_last_reset_delay_seconds",
+                "gauge",
+                self.last_reset_delay.load(Ordering::Relaxed),
+            ),
+        ];
+        let mut text = String::new();
+        for (name, kind, value) in counters {
+            let _ = write!(text, "# TYPE {} {}\n{} {}\n", name, kind, name, value);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::Metrics;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::default();
+        metrics.resets.fetch_add(2, Ordering::Relaxed);
+        metrics.last_reset_delay.store(1, Ordering::Relaxed);
+        let text = metrics.render();
+        assert!(text.contains("# TYPE coal_crank_resets_total counter\ncoal_crank_resets_total 2\n"));
+        assert!(text.contains("coal_crank_send_failures_total 0\n"));
+        assert!(text.contains("coal_crank_last_reset_delay_seconds 1\n"));
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
+};
+
+use coal_api::prelude::*;
+use coal_crank::{Cluster, Crank, CrankConfig, MintStatus, Result};
+use solana_program::{program_pack::Pack, rent::Rent};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account, signature::Signature, signer::Signer, transaction::Transaction,
+};
+use steel::*;
+use tokio::sync::Mutex;
+
+/// A cluster backed by solana-program-test, whose clock can be warped.
+struct BanksCluster {
+    context: Mutex<ProgramTestContext>,
+    payer: Pubkey,
+}
+
+impl BanksCluster {
+    async fn new() -> Self {
+        let context = setup().await;
+        let payer = context.payer.pubkey();
+        Self {
+            context: Mutex::new(context),
+            payer,
+        }
+    }
+
+    async fn warp_to_next_epoch(&self) {
+        let mut context = self.context.lock().await;
+        let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = clock.unix_timestamp.saturating_add(EPOCH_DURATION);
+        context.set_sysvar(&clock);
+    }
+
+    async fn set_crank_reward(&self, crank_reward: u64) {
+        let mut config = self.config(MINT_ADDRESS).await.unwrap();
+        config.crank_reward = crank_reward;
+        let mut context = self.context.lock().await;
+        context.set_account(
+            &config_pda(MINT_ADDRESS).0,
+            &program_account(config).into(),
+        );
+    }
+
+    async fn token_balance(&self, address: Pubkey) -> u64 {
+        let mut context = self.context.lock().await;
+        let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+}
+
+impl Cluster for BanksCluster {
+    fn payer(&self) -> Pubkey {
+        self.payer
+    }
+
+    async fn clock(&self) -> Result<Clock> {
+        let mut context = self.context.lock().await;
+        Ok(context.banks_client.get_sysvar::<Clock>().await?)
+    }
+
+    async fn config(&self, mint: Pubkey) -> Result<Config> {
+        let mut context = self.context.lock().await;
+        let account = context
+            .banks_client
+            .get_account(config_pda(mint).0)
+            .await?
+            .ok_or("Missing config")?;
+        Ok(*Config::try_from_bytes(&account.data)?)
+    }
+
+    async fn token_program(&self, mint: Pubkey) -> Result<Pubkey> {
+        let mut context = self.context.lock().await;
+        let account = context.banks_client.get_account(mint).await?.ok_or("Missing mint")?;
+        Ok(account.owner)
+    }
+
+    async fn send(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let mut context = self.context.lock().await;
+        let blockhash = context.get_new_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            blockhash,
+        );
+        let signature = tx.signatures[0];
+        context.banks_client.process_transaction(tx).await?;
+        Ok(signature)
+    }
+}
+
+/// A cluster whose first sends fail.
+struct FlakyCluster {
+    inner: BanksCluster,
+    failures: AtomicU32,
+}
+
+impl Cluster for FlakyCluster {
+    fn payer(&self) -> Pubkey {
+        self.inner.payer()
+    }
+
+    async fn clock(&self) -> Result<Clock> {
+        self.inner.clock().await
+    }
+
+    async fn config(&self, mint: Pubkey) -> Result<Config> {
+        self.inner.config(mint).await
+    }
+
+    async fn token_program(&self, mint: Pubkey) -> Result<Pubkey> {
+        self.inner.token_program(mint).await
+    }
+
+    async fn send(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let failures = self.failures.load(Ordering::Relaxed);
+        if failures.gt(&0) {
+            self.failures.store(failures - 1, Ordering::Relaxed);
+            return Err("Transaction dropped".into());
+        }
+        self.inner.send(ixs).await
+    }
+}
+
+/// A cluster where another crank lands a reset just before the first send.
+struct RacingCluster {
+    inner: BanksCluster,
+}
+
+impl Cluster for RacingCluster {
+    fn payer(&self) -> Pubkey {
+        self.inner.payer()
+    }
+
+    async fn clock(&self) -> Result<Clock> {
+        self.inner.clock().await
+    }
+
+    async fn config(&self, mint: Pubkey) -> Result<Config> {
+        self.inner.config(mint).await
+    }
+
+    async fn token_program(&self, mint: Pubkey) -> Result<Pubkey> {
+        self.inner.token_program(mint).await
+    }
+
+    async fn send(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let reset = coal_api::sdk::reset(MINT_ADDRESS, self.inner.payer());
+        self.inner.send(&[reset]).await?;
+        self.inner.send(ixs).await
+    }
+}
+
+fn crank_config(max_retries: u32) -> CrankConfig {
+    CrankConfig {
+        max_retries,
+        retry_delay: Duration::ZERO,
+        max_sleep: Duration::from_secs(EPOCH_DURATION as u64),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_tick_resets_at_epoch_boundary() {
+    let crank = Crank::new(BanksCluster::new().await, crank_config(0));
+    let metrics = crank.metrics();
+
+    // The first epoch has already ended, so the crank resets right away.
+    let tick = crank.tick().await.unwrap();
+    assert_eq!(tick.wait, Duration::from_secs(EPOCH_DURATION as u64));
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 1);
+    let [(mint, Ok(MintStatus::Reset { epoch, .. }))] = tick.mints.as_slice() else {
+        panic!("unexpected statuses: {:?}", tick.mints);
+    };
+    assert_eq!((*mint, *epoch), (MINT_ADDRESS, 1));
+    assert_eq!(metrics.resets.load(Ordering::Relaxed), 1);
+
+    // Before the boundary, the crank waits instead of sending a reset.
+    let tick = crank.tick().await.unwrap();
+    assert!(tick.wait.gt(&Duration::ZERO));
+    assert!(tick.wait.le(&Duration::from_secs(EPOCH_DURATION as u64)));
+    assert!(matches!(tick.mints[0].1, Ok(MintStatus::Waiting)));
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(metrics.resets.load(Ordering::Relaxed), 1);
+
+    // Once the clock passes the boundary, the crank resets again.
+    crank.cluster().warp_to_next_epoch().await;
+    crank.tick().await.unwrap();
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 2);
+    assert_eq!(metrics.resets.load(Ordering::Relaxed), 2);
+    assert_eq!(metrics.resets_skipped.load(Ordering::Relaxed), 0);
+    assert_eq!(metrics.send_failures.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn test_tick_retries_failed_sends() {
+    let cluster = FlakyCluster {
+        inner: BanksCluster::new().await,
+        failures: AtomicU32::new(2),
+    };
+    let crank = Crank::new(cluster, crank_config(3));
+    crank.tick().await.unwrap();
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(crank.metrics().send_failures.load(Ordering::Relaxed), 2);
+    assert_eq!(crank.metrics().resets.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn test_tick_gives_up_after_max_retries() {
+    let cluster = FlakyCluster {
+        inner: BanksCluster::new().await,
+        failures: AtomicU32::new(5),
+    };
+    let crank = Crank::new(cluster, crank_config(1));
+    let tick = crank.tick().await.unwrap();
+    assert_eq!(tick.wait, Duration::ZERO);
+    assert!(tick.mints[0].1.is_err());
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 0);
+    assert_eq!(crank.metrics().send_failures.load(Ordering::Relaxed), 2);
+    assert_eq!(crank.metrics().resets.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn test_tick_skips_epoch_reset_by_someone_else() {
+    let cluster = RacingCluster {
+        inner: BanksCluster::new().await,
+    };
+    let crank = Crank::new(cluster, crank_config(3));
+    let tick = crank.tick().await.unwrap();
+    assert!(matches!(tick.mints[0].1, Ok(MintStatus::Skipped)));
+    let config = crank.cluster().config(MINT_ADDRESS).await.unwrap();
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(crank.metrics().send_failures.load(Ordering::Relaxed), 1);
+    assert_eq!(crank.metrics().resets_skipped.load(Ordering::Relaxed), 1);
+    assert_eq!(crank.metrics().resets.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn test_tick_collects_crank_reward() {
+    let cluster = BanksCluster::new().await;
+    cluster.set_crank_reward(ONE_ORE / 100).await;
+    let crank = Crank::new(
+        cluster,
+        CrankConfig {
+            collect_rewards: true,
+            ..crank_config(0)
+        },
+    );
+    crank.tick().await.unwrap();
+    let payer = crank.cluster().payer();
+    let beneficiary =
+        spl_associated_token_account::get_associated_token_address(&payer, &MINT_ADDRESS);
+    assert_eq!(crank.cluster().token_balance(beneficiary).await, ONE_ORE / 100);
+    assert_eq!(crank.metrics().resets.load(Ordering::Relaxed), 1);
+}
+
+/// Starts a test validator with the busses, config, treasury and mint of an initialized program.
+async fn setup() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new(
+        "ore",
+        coal_api::ID,
+        processor!(ore::process_instruction),
+    );
+    for id in 0..BUS_COUNT {
+        let bus = Bus {
+            id: id as u64,
+            rewards: 0,
+            theoretical_rewards: 0,
+        };
+        program_test.add_account(bus_pda(MINT_ADDRESS, id as u8).0, program_account(bus));
+    }
+    let mut config = Config::zeroed();
+    config.mint = MINT_ADDRESS;
+    config.max_supply = ONE_ORE * 21_000_000;
+    config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+    config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+    config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+    program_test.add_account(config_pda(MINT_ADDRESS).0, program_account(config));
+    program_test.add_account(TREASURY_ADDRESS, program_account(Treasury {}));
+    program_test.add_account(
+        MINT_ADDRESS,
+        packed_account(spl_token::state::Mint {
+            mint_authority: Some(TREASURY_ADDRESS).into(),
+            supply: 0,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        }),
+    );
+    program_test.add_account(
+        spl_associated_token_account::get_associated_token_address(&TREASURY_ADDRESS, &MINT_ADDRESS),
+        packed_account(spl_token::state::Account {
+            mint: MINT_ADDRESS,
+            owner: TREASURY_ADDRESS,
+            amount: 0,
+            delegate: None.into(),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        }),
+    );
+    program_test.start_with_context().await
+}
+
+fn program_account<T: Discriminator + Pod>(state: T) -> Account {
+    let mut data = vec![0; 8 + std::mem::size_of::<T>()];
+    data[0] = T::discriminator();
+    data[8..].copy_from_slice(bytemuck::bytes_of(&state));
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: coal_api::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn packed_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
@@ -0,0 +1,45 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// Burn destroys a user's tokens and records them in the config's supply accounting.
+pub fn process_burn(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = Burn::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer_info, config_info, mint_info, sender_info, token_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+    mint_info
+        .is_writable()?
+        .has_address(&config.mint)?
+        .as_mint_interface()?;
+    sender_info
+        .is_writable()?
+        .as_token_account_interface()?
+        .assert(|t| t.owner == *signer_info.key)?
+        .assert(|t| t.mint == config.mint)?;
+    token_program.is_token_program()?;
+
+    // Burn tokens.
+    burn_interface(sender_info, mint_info, signer_info, token_program, amount)?;
+
+    // Update lifetime stats.
+    config.total_burned = config.total_burned.saturating_add(amount);
+
+    // Log data.
+    BurnEvent {
+        amount,
+        total_burned: config.total_burned,
+    }
+    .log();
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use coal_api::prelude::*;
-use ore_api;
+use ore_api::state::proof_pda as ore_proof_pda;
 use steel::*;
 
 /// Claim distributes claimable ORE from the treasury to a miner.
@@ -9,7 +9,7 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     let amount = u64::from_le_bytes(args.amount);
 
     // Load accounts.
-    let [signer_info, beneficiary_info, proof_info, mint_info, ore_proof_info, treasury_info, treasury_tokens_info, treasury_ore_tokens_info, ore_treasury_info, ore_treasury_tokens_info, token_program] =
+    let [signer_info, beneficiary_info, proof_info, config_info, mint_info, ore_proof_info, treasury_info, treasury_tokens_info, treasury_ore_tokens_info, ore_treasury_info, ore_treasury_tokens_info, token_program, ore_token_program, ore_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -17,17 +17,36 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     signer_info.is_signer()?;
     beneficiary_info
         .is_writable()?
-        .as_token_account()?
+        .as_token_account_interface()?
         .assert(|t| t.mint == *mint_info.key)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+    let mint = mint_info.as_mint_interface()?;
     let proof = proof_info
+        .has_seeds(
+            &[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref()],
+            &coal_api::ID,
+        )?
+        .has_current_layout::<Proof>()?
         .as_account_mut::<Proof>(&coal_api::ID)?
         .assert_mut_err(
             |p| p.authority == *signer_info.key,
             ProgramError::MissingRequiredSignature,
         )?;
+    ore_proof_info.has_address(&ore_proof_pda(*proof_info.key).0)?;
     treasury_info.is_treasury()?;
-    treasury_tokens_info.is_writable()?.is_treasury_tokens()?;
-    token_program.is_program(&spl_token::ID)?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account_interface(treasury_info.key, mint_info.key)?;
+    treasury_ore_tokens_info
+        .is_writable()?
+        .as_associated_token_account(treasury_info.key, &ORE_MINT_ADDRESS)?;
+    token_program.is_token_program()?;
+    ore_token_program.is_program(&spl_token::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
 
     // Update miner balance.
     proof.balance = proof
@@ -36,25 +55,32 @@ pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .ok_or(OreError::ClaimTooLarge)?;
 
     // Transfer tokens from treasury to beneficiary.
-    transfer_signed(
+    transfer_checked_signed(
         treasury_info,
         treasury_tokens_info,
+        mint_info,
         beneficiary_info,
         token_program,
         amount,
+        mint.decimals,
         &[TREASURY],
     )?;
 
-    // Claim remaining ORE to treasury when balance is 0.
+    // Track the treasury balance.
+    config.total_balance = config.total_balance.saturating_sub(amount);
+
+    // Claim remaining ORE to treasury when balance is 0, and count it towards the mint's backing.
     if proof.balance == 0 {
-        let ore_proof = ore_proof_info.as_account::<Proof>(&ore_api::ID)?;
+        let ore_proof = ore_proof_info.as_account::<ore_api::state::Proof>(&ore_api::ID)?;
+        config.ore_balance = config.ore_balance.saturating_add(ore_proof.balance);
         let claim_accounts = &[
             proof_info.clone(),
             treasury_ore_tokens_info.clone(),
             ore_proof_info.clone(), 
             ore_treasury_info.clone(),
             ore_treasury_tokens_info.clone(),
-            token_program.clone()
+            ore_token_program.clone(),
+            ore_program.clone(),
         ];
         let proof_bump = proof.bump as u8;
         
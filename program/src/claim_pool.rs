@@ -0,0 +1,83 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// ClaimPool distributes a member's part of a pool's rewards from the treasury.
+pub fn process_claim_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = ClaimPool::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer_info, beneficiary_info, pool_info, member_info, proof_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    beneficiary_info
+        .is_writable()?
+        .as_token_account_interface()?
+        .assert(|t| t.mint == *mint_info.key)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+    let mint = mint_info.as_mint_interface()?;
+    let pool = pool_info
+        .as_account::<Pool>(&coal_api::ID)?
+        .assert(|p| p.proof == *proof_info.key)
+        .map_err(|_| OreError::InvalidPool)?;
+    pool_info.has_seeds(
+        &[POOL, mint_info.key.as_ref(), pool.authority.as_ref()],
+        &coal_api::ID,
+    )?;
+    let member = member_info
+        .is_writable()?
+        .as_account_mut::<PoolMember>(&coal_api::ID)?
+        .assert_mut_err(
+            |m| m.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert_mut_err(|m| m.pool == *pool_info.key, OreError::InvalidPoolMember.into())?;
+    let proof = proof_info
+        .is_writable()?
+        .has_seeds(
+            &[PROOF, mint_info.key.as_ref(), pool_info.key.as_ref()],
+            &coal_api::ID,
+        )?
+        .has_current_layout::<Proof>()?
+        .as_account_mut::<Proof>(&coal_api::ID)?;
+    treasury_info.is_treasury()?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account_interface(treasury_info.key, mint_info.key)?;
+    token_program.is_token_program()?;
+
+    // Update member and pool balances.
+    member.balance = member
+        .balance
+        .checked_sub(amount)
+        .ok_or(OreError::ClaimTooLarge)?;
+    proof.balance = proof
+        .balance
+        .checked_sub(amount)
+        .ok_or(OreError::ClaimTooLarge)?;
+
+    // Transfer tokens from treasury to beneficiary.
+    transfer_checked_signed(
+        treasury_info,
+        treasury_tokens_info,
+        mint_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        mint.decimals,
+        &[TREASURY],
+    )?;
+
+    // Track the treasury balance.
+    config.total_balance = config.total_balance.saturating_sub(amount);
+
+    Ok(())
+}
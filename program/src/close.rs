@@ -1,29 +1,39 @@
 use coal_api::prelude::*;
-use ore_api;
+use ore_api::state::proof_pda as ore_proof_pda;
 use steel::*;
 
 /// Close closes a proof account and returns the rent to the owner.
 pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer_info, proof_info, ore_proof_info, system_program] = accounts else {
+    let [signer_info, proof_info, ore_proof_info, mint_info, system_program, ore_program] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    proof_info
+    let proof = proof_info
         .is_writable()?
-        .as_account::<Proof>(&ore_api::ID)?
+        .has_seeds(
+            &[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref()],
+            &coal_api::ID,
+        )?
+        .has_current_layout::<Proof>()?
+        .as_account::<Proof>(&coal_api::ID)?
         .assert_err(
             |p| p.authority == *signer_info.key,
             ProgramError::MissingRequiredSignature,
         )?
         .assert(|p| p.balance == 0)?;
+    ore_proof_info
+        .is_writable()?
+        .has_address(&ore_proof_pda(*proof_info.key).0)?;
     system_program.is_program(&system_program::ID)?;
-    
-    let proof = proof_info.as_account::<Proof>(&coal_api::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+
+    // Close the ORE proof, which returns its rent to the proof.
     let close_accounts = &[
         proof_info.clone(),
         ore_proof_info.clone(),
-        system_program.clone()
+        system_program.clone(),
+        ore_program.clone(),
     ];
     let proof_bump = proof.bump as u8;
     solana_program::program::invoke_signed(
@@ -31,7 +41,7 @@ pub fn process_close(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
             *proof_info.key,
         ),
         close_accounts,
-        &[&[PROOF, signer_info.key.as_ref(), &[proof_bump]]]
+        &[&[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref(), &[proof_bump]]]
     )?;
 
     // Return rent to signer.
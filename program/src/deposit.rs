@@ -0,0 +1,63 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// Deposit stakes tokens in a boost.
+pub fn process_deposit(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = Deposit::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let clock = Clock::get()?;
+    let [signer_info, boost_info, stake_info, sender_info, boost_tokens_info, mint_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let boost = boost_info
+        .is_writable()?
+        .as_account_mut::<Boost>(&coal_api::ID)?
+        .assert_mut(|b| b.mint == *mint_info.key)?;
+    let stake = stake_info
+        .is_writable()?
+        .as_account_mut::<Stake>(&coal_api::ID)?
+        .assert_mut_err(
+            |s| s.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert_mut_err(|s| s.boost == *boost_info.key, OreError::InvalidBoost.into())?;
+    sender_info
+        .is_writable()?
+        .as_token_account_interface()?
+        .assert(|t| t.mint == *mint_info.key)?;
+    boost_tokens_info
+        .is_writable()?
+        .as_associated_token_account_interface(boost_info.key, mint_info.key)?;
+    let mint = mint_info.as_mint_interface()?;
+    token_program.is_token_program()?;
+
+    // Update balances.
+    boost.total_stake = boost
+        .total_stake
+        .checked_add(amount)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    stake.balance = stake
+        .balance
+        .checked_add(amount)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    stake.last_deposit_at = clock.unix_timestamp;
+
+    // Transfer tokens from the sender to the boost.
+    transfer_checked_interface(
+        signer_info,
+        sender_info,
+        mint_info,
+        boost_tokens_info,
+        token_program,
+        amount,
+        mint.decimals,
+    )?;
+
+    Ok(())
+}
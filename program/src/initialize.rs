@@ -1,16 +1,25 @@
 use coal_api::prelude::*;
 use ore_api::consts::{MINT as ORE_MINT, MINT_NOISE as ORE_MINT_NOISE};
-use solana_program::program_pack::Pack;
+use solana_program::{program_pack::Pack, rent::Rent};
 use spl_token::state::Mint;
+use spl_token_2022::extension::{metadata_pointer, ExtensionType};
+use spl_token_metadata_interface::state::TokenMetadata;
 use steel::*;
 
 /// Initialize sets up the ORE program to begin mining.
 pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = Initialize::try_from_bytes(data)?;
+    let max_supply = u64::from_le_bytes(args.max_supply);
+    let schedule_epochs = u64::from_le_bytes(args.schedule_epochs);
+    let decay_basis_points = u64::from_le_bytes(args.decay_basis_points);
+    let recycle_burns = u64::from_le_bytes(args.recycle_burns);
+    let migration_ratio = u64::from_le_bytes(args.migration_ratio);
+    let migration_deadline = u64::from_le_bytes(args.migration_deadline);
+    let crank_reward = u64::from_le_bytes(args.crank_reward);
 
     // Load accounts.
-    let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, metadata_info, mint_info, ore_mint_info, treasury_info, treasury_tokens_info, ore_treasury_tokens_info, system_program, token_program, associated_token_program, metadata_program, rent_sysvar] =
+    let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, metadata_info, mint_info, ore_mint_info, treasury_info, treasury_tokens_info, ore_treasury_tokens_info, system_program, token_program, ore_token_program, associated_token_program, metadata_program, rent_sysvar] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -52,14 +61,6 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         .is_empty()?
         .is_writable()?
         .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?;
-    metadata_info.is_empty()?.is_writable()?.has_seeds(
-        &[
-            METADATA,
-            mpl_token_metadata::ID.as_ref(),
-            mint_info.key.as_ref().as_ref(),
-        ],
-        &mpl_token_metadata::ID,
-    )?;
     mint_info
         .is_empty()?
         .is_writable()?
@@ -72,25 +73,38 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         .has_seeds(&[TREASURY], &coal_api::ID)?;
     treasury_tokens_info.is_empty()?.is_writable()?;
     system_program.is_program(&system_program::ID)?;
-    token_program.is_program(&spl_token::ID)?;
+    token_program.is_token_program()?;
+    ore_token_program.is_program(&spl_token::ID)?;
     associated_token_program.is_program(&spl_associated_token_account::ID)?;
-    metadata_program.is_program(&mpl_token_metadata::ID)?;
     rent_sysvar.is_sysvar(&sysvar::rent::ID)?;
 
+    // Token-2022 mints hold their own metadata, so the Metaplex accounts are only used for SPL Token.
+    if token_program.key.eq(&spl_token::ID) {
+        metadata_info.is_empty()?.is_writable()?.has_seeds(
+            &[
+                METADATA,
+                mpl_token_metadata::ID.as_ref(),
+                mint_info.key.as_ref(),
+            ],
+            &mpl_token_metadata::ID,
+        )?;
+        metadata_program.is_program(&mpl_token_metadata::ID)?;
+    }
+
     // Initialize bus accounts.
     let bus_infos = [
         bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
         bus_7_info,
     ];
-    for i in 0..BUS_COUNT {
+    for (i, bus_info) in bus_infos.iter().enumerate() {
         create_account::<Bus>(
-            bus_infos[i],
+            bus_info,
             system_program,
             signer_info,
-            &ore_api::ID,
+            &coal_api::ID,
             &[BUS, mint_info.key.as_ref(), &[i as u8]],
         )?;
-        let bus = bus_infos[i].as_account_mut::<Bus>(&ore_api::ID)?;
+        let bus = bus_info.as_account_mut::<Bus>(&coal_api::ID)?;
         bus.id = i as u64;
         bus.rewards = 0;
         bus.theoretical_rewards = 0;
@@ -104,19 +118,34 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
         &coal_api::ID,
         &[CONFIG, mint_info.key.as_ref()],
     )?;
-    let config = config_info.as_account_mut::<Config>(&ore_api::ID)?;
+    let config = config_info.as_account_mut::<Config>(&coal_api::ID)?;
     config.mint = *mint_info.key;
-    config.max_supply = args.max_supply;
+    config.migration_mint = args.migration_mint;
+    config.migration_ratio = migration_ratio;
+    config.migration_deadline = migration_deadline;
+    config.max_supply = max_supply;
     config.current_epoch = 0;
     config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
-    config.schedule_epochs = args.schedule_epochs;
-    config.decay_basis_points = args.decay_basis_points;
+    config.schedule_epochs = schedule_epochs;
+    config.decay_basis_points = decay_basis_points;
     config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
     config.last_reset_at = 0;
     config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
     config.total_balance = 0;
-    config.burned_ingredient = args.burned_ingredient;
-    config.wrapped_ingredient = args.wrapped_ingredient;
+    config.burned_ingredient = Ingredient {
+        mint: args.burned_ingredient_mint,
+        ratio: f64::from_le_bytes(args.burned_ingredient_ratio),
+    };
+    config.wrapped_ingredient = Ingredient {
+        mint: args.wrapped_ingredient_mint,
+        ratio: f64::from_le_bytes(args.wrapped_ingredient_ratio),
+    };
+    config.total_redeemed = 0;
+    config.total_ore_redeemed = 0;
+    config.ore_balance = 0;
+    config.total_burned = 0;
+    config.recycle_burns = recycle_burns;
+    config.crank_reward = crank_reward;
 
     if treasury_info.data_len() == 0 {
         // Initialize treasury.
@@ -130,54 +159,75 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
     }
 
     // Initialize mint.
-    allocate_account_with_bump(
-        mint_info,
-        system_program,
-        signer_info,
-        Mint::LEN,
-        &spl_token::ID,
-        &[MINT, args.mint_noise.as_slice()],
-        args.mint_bump as u8,
-    )?;
-    initialize_mint_signed_with_bump(
-        mint_info,
-        treasury_info,
-        None,
-        token_program,
-        rent_sysvar,
-        TOKEN_DECIMALS,
-        &[MINT, args.mint_noise.as_slice()],
-        args.mint_bump as u8,
-    )?;
+    let metadata_name = bytes_to_trimmed_string(&args.metadata_name);
+    let metadata_symbol = bytes_to_trimmed_string(&args.metadata_symbol);
+    let metadata_uri = bytes_to_trimmed_string(&args.metadata_uri);
+    if token_program.key.eq(&spl_token_2022::ID) {
+        initialize_mint_2022(
+            signer_info,
+            mint_info,
+            treasury_info,
+            system_program,
+            token_program,
+            &[MINT, args.mint_noise.as_slice()],
+            args.mint_bump,
+            TokenMetadata {
+                name: metadata_name,
+                symbol: metadata_symbol,
+                uri: metadata_uri,
+                ..Default::default()
+            },
+        )?;
+    } else {
+        allocate_account_with_bump(
+            mint_info,
+            system_program,
+            signer_info,
+            Mint::LEN,
+            &spl_token::ID,
+            &[MINT, args.mint_noise.as_slice()],
+            args.mint_bump,
+        )?;
+        initialize_mint_signed_with_bump(
+            mint_info,
+            treasury_info,
+            None,
+            token_program,
+            rent_sysvar,
+            TOKEN_DECIMALS,
+            &[MINT, args.mint_noise.as_slice()],
+            args.mint_bump,
+        )?;
 
-    // Initialize mint metadata.
-    mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi {
-        __program: metadata_program,
-        metadata: metadata_info,
-        mint: mint_info,
-        mint_authority: treasury_info,
-        payer: signer_info,
-        update_authority: (signer_info, true),
-        system_program,
-        rent: Some(rent_sysvar),
-        __args: mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
-            data: mpl_token_metadata::types::DataV2 {
-                name: bytes_to_trimmed_string(&args.metadata_name),
-                symbol: bytes_to_trimmed_string(&args.metadata_symbol),
-                uri: bytes_to_trimmed_string(&args.metadata_uri),
-                seller_fee_basis_points: 0,
-                creators: None,
-                collection: None,
-                uses: None,
+        // Initialize mint metadata.
+        mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi {
+            __program: metadata_program,
+            metadata: metadata_info,
+            mint: mint_info,
+            mint_authority: treasury_info,
+            payer: signer_info,
+            update_authority: (signer_info, true),
+            system_program,
+            rent: Some(rent_sysvar),
+            __args: mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
+                data: mpl_token_metadata::types::DataV2 {
+                    name: metadata_name,
+                    symbol: metadata_symbol,
+                    uri: metadata_uri,
+                    seller_fee_basis_points: 0,
+                    creators: None,
+                    collection: None,
+                    uses: None,
+                },
+                is_mutable: true,
+                collection_details: None,
             },
-            is_mutable: true,
-            collection_details: None,
-        },
+        }
+        .invoke_signed(&[&[TREASURY, &[TREASURY_BUMP]]])?;
     }
-    .invoke_signed(&[&[TREASURY, &[TREASURY_BUMP]]])?;
 
     // Initialize treasury token account.
-    create_associated_token_account(
+    create_associated_token_account_interface(
         signer_info,
         treasury_info,
         treasury_tokens_info,
@@ -195,7 +245,7 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
             ore_treasury_tokens_info,
             ore_mint_info,
             system_program,
-            token_program,
+            ore_token_program,
             associated_token_program,
         )?;
     }
@@ -203,6 +253,89 @@ pub fn process_initialize(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramR
     Ok(())
 }
 
+/// Creates a Token-2022 mint whose metadata pointer refers to the mint itself, and writes the
+/// token metadata into the mint's extension data.
+#[allow(clippy::too_many_arguments)]
+fn initialize_mint_2022<'info>(
+    signer_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    treasury_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    bump: u8,
+    metadata: TokenMetadata,
+) -> ProgramResult {
+    // Allocate the mint with room for the metadata pointer.
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::MetadataPointer,
+    ])?;
+    allocate_account_with_bump(
+        mint_info,
+        system_program,
+        signer_info,
+        space,
+        &spl_token_2022::ID,
+        seeds,
+        bump,
+    )?;
+
+    // Fund the metadata up front, since the token program reallocs the mint without paying rent.
+    let lamports = Rent::get()?
+        .minimum_balance(space.saturating_add(metadata.tlv_size_of()?))
+        .saturating_sub(mint_info.lamports());
+    solana_program::program::invoke(
+        &solana_program::system_instruction::transfer(signer_info.key, mint_info.key, lamports),
+        &[
+            signer_info.clone(),
+            mint_info.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    // Point the metadata at the mint and initialize the mint.
+    solana_program::program::invoke(
+        &metadata_pointer::instruction::initialize(
+            &spl_token_2022::ID,
+            mint_info.key,
+            Some(*signer_info.key),
+            Some(*mint_info.key),
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )?;
+    solana_program::program::invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            mint_info.key,
+            treasury_info.key,
+            None,
+            TOKEN_DECIMALS,
+        )?,
+        &[token_program.clone(), mint_info.clone()],
+    )?;
+
+    // Initialize mint metadata.
+    solana_program::program::invoke_signed(
+        &spl_token_metadata_interface::instruction::initialize(
+            &spl_token_2022::ID,
+            mint_info.key,
+            signer_info.key,
+            mint_info.key,
+            treasury_info.key,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ),
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            signer_info.clone(),
+            treasury_info.clone(),
+        ],
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )
+}
+
 fn bytes_to_trimmed_string(vec: &[u8]) -> String {
     String::from_utf8(vec.to_vec())
         .unwrap()
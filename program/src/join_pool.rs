@@ -0,0 +1,42 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// JoinPool creates a member account to track a member's shares in a pool.
+pub fn process_join_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let _args = JoinPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, pool_info, member_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let pool = pool_info
+        .is_writable()?
+        .as_account_mut::<Pool>(&coal_api::ID)?;
+    member_info.is_empty()?.is_writable()?.has_seeds(
+        &[POOL_MEMBER, pool_info.key.as_ref(), signer_info.key.as_ref()],
+        &coal_api::ID,
+    )?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Initialize member.
+    let (_, member_bump) = pool_member_pda(*pool_info.key, *signer_info.key);
+    create_account::<PoolMember>(
+        member_info,
+        system_program,
+        signer_info,
+        &coal_api::ID,
+        &[POOL_MEMBER, pool_info.key.as_ref(), signer_info.key.as_ref()],
+    )?;
+    let member = member_info.as_account_mut::<PoolMember>(&coal_api::ID)?;
+    member.authority = *signer_info.key;
+    member.pool = *pool_info.key;
+    member.total_hashes = 0;
+    member.total_difficulty = 0;
+    member.bump = member_bump as u64;
+    member.id = pool.total_members;
+    pool.total_members = pool.total_members.saturating_add(1);
+
+    Ok(())
+}
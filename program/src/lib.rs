@@ -1,36 +1,81 @@
+mod burn;
 mod claim;
+#[cfg(feature = "pools")]
+mod claim_pool;
 mod close;
+#[cfg(feature = "boosts")]
+mod deposit;
 mod initialize;
+#[cfg(feature = "pools")]
+mod join_pool;
+mod migrate;
 mod mine;
+#[cfg(feature = "pools")]
+mod mine_pool;
+#[cfg(feature = "boosts")]
+mod new_boost;
 mod open;
+#[cfg(feature = "pools")]
+mod open_pool;
+#[cfg(feature = "boosts")]
+mod open_stake;
+mod redeem;
 mod reset;
-mod stake;
+mod set_migration;
+mod set_mine_policy;
+#[cfg(feature = "pools")]
+mod submit_pool;
 mod update;
+#[cfg(feature = "upgrade")]
 mod upgrade;
+#[cfg(feature = "boosts")]
+mod withdraw;
 
+use burn::*;
 use claim::*;
+#[cfg(feature = "pools")]
+use claim_pool::*;
 use close::*;
+#[cfg(feature = "boosts")]
+use deposit::*;
 use initialize::*;
+#[cfg(feature = "pools")]
+use join_pool::*;
+use migrate::*;
 use mine::*;
+#[cfg(feature = "pools")]
+use mine_pool::*;
+#[cfg(feature = "boosts")]
+use new_boost::*;
 use open::*;
+#[cfg(feature = "pools")]
+use open_pool::*;
+#[cfg(feature = "boosts")]
+use open_stake::*;
+use redeem::*;
 use reset::*;
-use stake::*;
+use set_migration::*;
+use set_mine_policy::*;
+#[cfg(feature = "pools")]
+use submit_pool::*;
 use update::*;
+#[cfg(feature = "upgrade")]
 use upgrade::*;
+#[cfg(feature = "boosts")]
+use withdraw::*;
 
-use ore_api::instruction::*;
+use coal_api::{error::OreError, instruction::*};
 // use solana_include_idl::{include_idl, parse::IdlType};
 use steel::*;
 
 // include_idl!(IdlType::Codama, concat!(env!("OUT_DIR"), "/codama.idl.zip"));
 
-#[allow(deprecated)]
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     data: &[u8],
 ) -> ProgramResult {
-    let (ix, data) = parse_instruction(&ore_api::ID, program_id, data)?;
+    let (ix, data) = parse_instruction(&coal_api::ID, program_id, data)?;
 
     match ix {
         OreInstruction::Claim => process_claim(accounts, data)?,
@@ -38,10 +83,36 @@ pub fn process_instruction(
         OreInstruction::Mine => process_mine(accounts, data)?,
         OreInstruction::Open => process_open(accounts, data)?,
         OreInstruction::Reset => process_reset(accounts, data)?,
-        OreInstruction::Stake => process_stake(accounts, data)?,
         OreInstruction::Update => process_update(accounts, data)?,
+        #[cfg(feature = "upgrade")]
         OreInstruction::Upgrade => process_upgrade(accounts, data)?,
+        OreInstruction::Redeem => process_redeem(accounts, data)?,
+        OreInstruction::Burn => process_burn(accounts, data)?,
+        #[cfg(feature = "pools")]
+        OreInstruction::OpenPool => process_open_pool(accounts, data)?,
+        #[cfg(feature = "pools")]
+        OreInstruction::JoinPool => process_join_pool(accounts, data)?,
+        #[cfg(feature = "pools")]
+        OreInstruction::MinePool => process_mine_pool(accounts, data)?,
+        #[cfg(feature = "pools")]
+        OreInstruction::SubmitPool => process_submit_pool(accounts, data)?,
+        #[cfg(feature = "pools")]
+        OreInstruction::ClaimPool => process_claim_pool(accounts, data)?,
+        #[cfg(feature = "boosts")]
+        OreInstruction::OpenStake => process_open_stake(accounts, data)?,
+        #[cfg(feature = "boosts")]
+        OreInstruction::Deposit => process_deposit(accounts, data)?,
+        #[cfg(feature = "boosts")]
+        OreInstruction::Withdraw => process_withdraw(accounts, data)?,
+        OreInstruction::Migrate => process_migrate(accounts, data)?,
         OreInstruction::Initialize => process_initialize(accounts, data)?,
+        OreInstruction::SetMigration => process_set_migration(accounts, data)?,
+        OreInstruction::SetMinePolicy => process_set_mine_policy(accounts, data)?,
+        #[cfg(feature = "boosts")]
+        OreInstruction::NewBoost => process_new_boost(accounts, data)?,
+
+        // Retired instructions, and instructions compiled out of this build.
+        _ => return Err(OreError::Deprecated.into()),
     }
 
     Ok(())
@@ -0,0 +1,67 @@
+use coal_api::prelude::*;
+use solana_program::rent::Rent;
+use steel::*;
+
+/// Migrate grows an account created under an earlier layout to the current one.
+///
+/// The fields added since are zero-filled, which leaves their features off, except for the
+/// migration ratio of a config, which is set to the 1:1 rate configs upgraded at before it was
+/// configurable. A config grown past its ORE backing starts with none, so ORE swept into the
+/// treasury before the migration is not redeemable for its mint. The signer pays the rent for the
+/// extra space. Accounts already at the current layout are left as they are.
+pub fn process_migrate(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer_info, account_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    account_info.is_writable()?.has_owner(&coal_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Find the current size of the account, checking its address.
+    let len = account_info.data_len();
+    let (discriminator, mint) = {
+        let data = account_info.try_borrow_data()?;
+        let mint = data.get(8..40).and_then(|mint| Pubkey::try_from(mint).ok());
+        (data.first().copied(), mint)
+    };
+    let size = match (discriminator, mint) {
+        (Some(discriminator), Some(mint)) if discriminator == Config::discriminator() => {
+            account_info.has_seeds(&[CONFIG, mint.as_ref()], &coal_api::ID)?;
+            8 + std::mem::size_of::<Config>()
+        }
+        // The proof seeds include a mint the proof does not store, but only this program creates
+        // accounts it owns with the proof discriminator.
+        (Some(discriminator), _) if discriminator == Proof::discriminator() => {
+            8 + std::mem::size_of::<Proof>()
+        }
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    if len >= size {
+        return Ok(());
+    }
+
+    // Fund the rent of the extra space, and grow the account.
+    let lamports = Rent::get()?
+        .minimum_balance(size)
+        .saturating_sub(account_info.lamports());
+    solana_program::program::invoke(
+        &solana_program::system_instruction::transfer(signer_info.key, account_info.key, lamports),
+        &[
+            signer_info.clone(),
+            account_info.clone(),
+            system_program.clone(),
+        ],
+    )?;
+    account_info.realloc(size, true)?;
+
+    // Keep upgrades of legacy configs at the rate they had.
+    if discriminator == Some(Config::discriminator())
+        && len <= 8 + std::mem::offset_of!(Config, migration_ratio)
+    {
+        let config = account_info.as_account_mut::<Config>(&coal_api::ID)?;
+        config.migration_ratio = 10_000;
+    }
+
+    Ok(())
+}
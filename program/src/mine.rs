@@ -1,9 +1,11 @@
-use std::ops::Div;
-
 use drillx::Solution;
 use coal_api::prelude::*;
-use ore_api;
-use ore_boost_api;
+use ore_api::{
+    consts::{BUS_ADDRESSES as ORE_BUS_ADDRESSES, TREASURY_TOKENS_ADDRESS as ORE_TREASURY_TOKENS_ADDRESS},
+    state::proof_pda as ore_proof_pda,
+};
+use ore_boost_api::state::{directory_pda, reservation_pda};
+use solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use steel::*;
 
 /// Mine validates hashes and increments a miner's claimable balance.
@@ -11,35 +13,102 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = Mine::try_from_bytes(data)?;
 
+    // Mine and log data.
+    mine(accounts, args, 0)?.log_return();
+
+    Ok(())
+}
+
+/// Submits a hash for the proof and credits its reward, enforcing the config's mine policy plus
+/// the given policy flags.
+pub(crate) fn mine(
+    accounts: &[AccountInfo],
+    args: &Mine,
+    required_policy: u64,
+) -> Result<MineEvent, ProgramError> {
     // Load accounts.
     let clock = Clock::get()?;
     let t: i64 = clock.unix_timestamp;
     
-    let (required_accounts, optional_accounts) = accounts.split_at(14);
-    let split_index = optional_accounts.iter().position(|acc| acc.owner.eq(&ore_boost_api::ID)).unwrap_or(optional_accounts.len());
-    let (coal_optional_accounts, boost_accounts) = optional_accounts.split_at(split_index);
-    let [signer_info, coal_mint_info, bus_info, config_info, proof_info, ore_bus_info, ore_config_info, ore_proof_info, ore_directory_info, ore_reservation_info, ore_treasury_tokens_info, instructions_sysvar, slot_hashes_sysvar] =
+    let MineAccounts {
+        required: required_accounts,
+        ore_boosts: boost_accounts,
+        coal_boosts: coal_boost_accounts,
+    } = split_mine_accounts(accounts, args)?;
+    let [signer_info, coal_mint_info, bus_info, config_info, proof_info, ore_bus_info, ore_config_info, ore_proof_info, ore_directory_info, ore_reservation_info, ore_treasury_tokens_info, instructions_sysvar, slot_hashes_sysvar, ore_program, ore_boost_program] =
         required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    let bus = bus_info.is_bus()?.as_account_mut::<Bus>(&coal_api::ID)?;
     let config = config_info
-        .is_config()?
-        .as_account::<Config>(&coal_api::ID)?
-        .assert_err(
+        .is_writable()?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?
+        .assert_mut_err(
             |c| c.last_reset_at.saturating_add(EPOCH_DURATION) > t,
             OreError::NeedsReset.into(),
         )?;
+    coal_mint_info.has_address_err(&config.mint, OreError::InvalidMint)?;
+    let bus = bus_info
+        .is_writable()?
+        .as_account_mut::<Bus>(&coal_api::ID)?;
+    bus_info.has_address_err(&bus_pda(config.mint, bus.id as u8).0, OreError::InvalidBus)?;
     let proof = proof_info
+        .is_writable()?
+        .has_current_layout::<Proof>()?
         .as_account_mut::<Proof>(&coal_api::ID)?
         .assert_mut_err(
             |p| p.miner == *signer_info.key,
             ProgramError::MissingRequiredSignature,
         )?;
+    let proof_address = Pubkey::create_program_address(
+        &[PROOF, config.mint.as_ref(), proof.authority.as_ref(), &[proof.bump as u8]],
+        &coal_api::ID,
+    )
+    .map_err(|_| OreError::InvalidProof)?;
+    proof_info.has_address_err(&proof_address, OreError::InvalidProof)?;
+
+    // The ORE accounts must be the ones derived from the proof, so the CPIs act on this miner.
+    let ore_proof_address = ore_proof_pda(*proof_info.key).0;
+    ore_proof_info.has_address_err(&ore_proof_address, OreError::InvalidOreAccounts)?;
+    ore_reservation_info
+        .has_address_err(&reservation_pda(ore_proof_address).0, OreError::InvalidOreAccounts)?;
+    ore_directory_info.has_address_err(&directory_pda().0, OreError::InvalidOreAccounts)?;
+    ore_config_info.has_address_err(&ORE_CONFIG_ADDRESS, OreError::InvalidOreAccounts)?;
+    ore_treasury_tokens_info
+        .has_address_err(&ORE_TREASURY_TOKENS_ADDRESS, OreError::InvalidOreAccounts)?;
+    if !ORE_BUS_ADDRESSES.contains(ore_bus_info.key) {
+        return Err(OreError::InvalidOreAccounts.into());
+    }
     instructions_sysvar.is_sysvar(&sysvar::instructions::ID)?;
     slot_hashes_sysvar.is_sysvar(&sysvar::slot_hashes::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    ore_boost_program.is_program(&ore_boost_api::ID)?;
+
+    // Enforce the anti-cheat policy by introspecting the other instructions of the transaction.
+    let mine_policy = config.mine_policy | required_policy;
+    if mine_policy.ne(&0) {
+        let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+        let mut instructions = vec![];
+        while let Ok(ix) = load_instruction_at_checked(instructions.len(), instructions_sysvar) {
+            instructions.push(ix);
+        }
+        check_mine_policy(
+            mine_policy,
+            &config.mine_cpi_allowlist,
+            proof_info.key,
+            &instructions,
+            current_index,
+        )?;
+    }
+
+    // Mines invoked by CPI are hidden from the introspection above, so the proof also records the
+    // slot it last mined in, which allows one hash per proof per slot.
+    if mine_policy & MINE_POLICY_SINGLE_MINE != 0 && proof.last_mine_slot == clock.slot {
+        return Err(OreError::TooManyMines.into());
+    }
+    proof.last_mine_slot = clock.slot;
 
      // Submit solution to the ORE program
      let solution = Solution::new(args.digest, args.nonce);
@@ -53,8 +122,10 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
          ore_proof_info.clone(),
          instructions_sysvar.clone(),
          slot_hashes_sysvar.clone(),
+         ore_program.clone(),
      ];
      if let [boost_info, _boost_proof_info, reservation_info] = boost_accounts {
+        boost_info.has_owner(&ore_boost_api::ID)?;
         boost_keys = Some((*boost_info.key, *reservation_info.key));
         mine_accounts.extend_from_slice(boost_accounts);
     }
@@ -77,6 +148,7 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         ore_proof_info.clone(),
         ore_reservation_info.clone(),
         ore_treasury_tokens_info.clone(),
+        ore_boost_program.clone(),
     ];
 
     solana_program::program::invoke_signed(
@@ -85,43 +157,53 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
             *ore_proof_info.key
         ),
         &rotate_accounts,
-        &[&[PROOF, config.mint.as_ref(), signer_info.key.as_ref(), &[proof.bump as u8]]]
+        &[&[PROOF, config.mint.as_ref(), proof.authority.as_ref(), &[proof.bump as u8]]]
     )?;     
 
-    // Normalize the difficulty and calculate the reward amount.
-    //
-    // The reward doubles for every bit of difficulty (leading zeros) on the hash. We use the normalized
-    // difficulty so the minimum accepted difficulty pays out at the base reward rate.
-    let normalized_difficulty = difficulty
-        .checked_sub(config.min_difficulty as u32)
-        .unwrap();
-    let mut reward = config
-        .base_reward_rate
-        .checked_mul(2u64.checked_pow(normalized_difficulty).unwrap())
-        .unwrap();
-
-    // Apply boosts.
+    // Calculate the reward amount, capped by the bus limit.
     //
     // Boosts are staking incentives that can multiply a miner's rewards. Up to 3 boosts can be applied
-    // on any given mine operation.
-    // TODO handle COAL boosts with coal_optional_accounts
-
-    // Apply bus limit.
-    //
-    // Busses are limited to distributing 1 ORE per epoch. The payout amount must be capped to whatever is
-    // left in the selected bus. This limits the maximum amount that will be paid out for any given hash to 1 ORE.
-    let reward_actual = reward.min(bus.rewards).min(config.get_epoch_rewards().saturating_div(BUS_COUNT as u64));
+    // on any given mine operation. Each pays a bonus proportional to the proof authority's share of
+    // the boost's stake, still capped by the bus limit.
+    let reward = compute_mine_reward(config, bus, difficulty)?;
+    let mut reward_actual = reward.actual;
+    for (i, boost_accounts) in coal_boost_accounts.chunks(COAL_BOOST_ACCOUNTS).enumerate() {
+        let [boost_info, stake_info] = boost_accounts else {
+            return Err(OreError::InvalidMineAccounts.into());
+        };
+        if coal_boost_accounts[..i * COAL_BOOST_ACCOUNTS]
+            .iter()
+            .any(|a| a.key.eq(boost_info.key))
+        {
+            return Err(OreError::InvalidBoost.into());
+        }
+        let boost = boost_info.as_account::<Boost>(&coal_api::ID)?;
+        let stake = stake_info
+            .as_account::<Stake>(&coal_api::ID)?
+            .assert_err(
+                |s| s.boost == *boost_info.key && s.authority == proof.authority,
+                OreError::InvalidBoost.into(),
+            )?;
+        reward_actual = apply_boost(reward_actual, reward.actual, boost, stake, t, bus.rewards);
+    }
 
     // Update balances.
     //
     // We track the theoretical rewards that would have been paid out ignoring the bus limit, so the
     // base reward rate will be updated to account for the real hashpower on the network.
-    bus.theoretical_rewards = bus.theoretical_rewards.checked_add(reward).unwrap();
-    bus.rewards = bus.rewards.checked_sub(reward_actual).unwrap();
-    proof.balance = proof.balance.checked_add(reward_actual).unwrap();
+    bus.theoretical_rewards = bus.theoretical_rewards.saturating_add(reward.theoretical);
+    bus.rewards = bus
+        .rewards
+        .checked_sub(reward_actual)
+        .ok_or(OreError::ArithmeticOverflow)?;
+    proof.balance = proof
+        .balance
+        .checked_add(reward_actual)
+        .ok_or(OreError::ArithmeticOverflow)?;
 
     proof.total_hashes = proof.total_hashes.saturating_add(1);
     proof.total_rewards = proof.total_rewards.saturating_add(reward_actual);
+    config.total_rewards = config.total_rewards.saturating_add(reward_actual);
 
     // Log data.
     //
@@ -134,12 +216,289 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     //         .checked_div(reward_pre_penalty as u128)
     //         .unwrap() as u64;
     // }
-    MineEvent {
+    Ok(MineEvent {
         balance: proof.balance,
         difficulty: difficulty as u64,
         reward: reward_actual,
+    })
+}
+
+/// Number of accounts every mine instruction starts with.
+const MINE_ACCOUNTS: usize = 15;
+
+/// Number of accounts of an ORE boost: the boost, its ORE proof, and the reservation.
+const ORE_BOOST_ACCOUNTS: usize = 3;
+
+/// Number of accounts of a COAL boost: the boost and the proof authority's stake.
+const COAL_BOOST_ACCOUNTS: usize = 2;
+
+/// The accounts of a mine instruction, split by the counts in the args.
+#[derive(Debug, PartialEq)]
+struct MineAccounts<'a, T> {
+    required: &'a [T],
+    ore_boosts: &'a [T],
+    coal_boosts: &'a [T],
+}
+
+/// Splits the mine accounts into the required accounts, the ORE boost accounts, and the COAL
+/// boost accounts, using the counts in the args.
+fn split_mine_accounts<'a, T>(
+    accounts: &'a [T],
+    args: &Mine,
+) -> Result<MineAccounts<'a, T>, ProgramError> {
+    let ore_boost_accounts = args.ore_boost_accounts as usize;
+    if ore_boost_accounts.ne(&0) && ore_boost_accounts.ne(&ORE_BOOST_ACCOUNTS) {
+        return Err(OreError::InvalidMineAccounts.into());
+    }
+    let coal_boost_accounts = args.coal_boost_accounts as usize;
+    if coal_boost_accounts % COAL_BOOST_ACCOUNTS != 0
+        || coal_boost_accounts.gt(&(COAL_BOOST_ACCOUNTS * MAX_COAL_BOOSTS))
+    {
+        return Err(OreError::InvalidMineAccounts.into());
+    }
+    let len = MINE_ACCOUNTS + ore_boost_accounts + coal_boost_accounts;
+    if accounts.len().lt(&len) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    if accounts.len().gt(&len) {
+        return Err(OreError::InvalidMineAccounts.into());
+    }
+    let (required, optional_accounts) = accounts.split_at(MINE_ACCOUNTS);
+    let (ore_boosts, coal_boosts) = optional_accounts.split_at(ore_boost_accounts);
+    Ok(MineAccounts {
+        required,
+        ore_boosts,
+        coal_boosts,
+    })
+}
+
+/// Checks the transaction's top-level instructions against the mine policy flags.
+///
+/// Mines invoked by CPI show up as the top-level instruction of the invoking program, so only
+/// mines sent directly to this program are counted here. `mine` catches the rest of
+/// `MINE_POLICY_SINGLE_MINE` with the proof's last mined slot.
+fn check_mine_policy(
+    mine_policy: u64,
+    mine_cpi_allowlist: &[Pubkey],
+    proof: &Pubkey,
+    instructions: &[Instruction],
+    current_index: usize,
+) -> Result<(), OreError> {
+    let current = instructions
+        .get(current_index)
+        .ok_or(OreError::InvalidMineAccounts)?;
+
+    // A mine invoked by another program must come from an allowed program.
+    if mine_policy & MINE_POLICY_CPI_ALLOWLIST != 0
+        && current.program_id.ne(&coal_api::ID)
+        && !mine_cpi_allowlist.contains(&current.program_id)
+    {
+        return Err(OreError::CpiNotAllowed);
+    }
+
+    // The transaction must not submit more than one hash, whichever instruction submits it.
+    if mine_policy & MINE_POLICY_SINGLE_MINE != 0 {
+        let mines = instructions
+            .iter()
+            .filter(|ix| {
+                ix.program_id.eq(&coal_api::ID)
+                    && ix.data.first().is_some_and(|d| {
+                        [OreInstruction::Mine, OreInstruction::MinePool, OreInstruction::SubmitPool]
+                            .iter()
+                            .any(|i| *i as u8 == *d)
+                    })
+            })
+            .count();
+        if mines.gt(&1) {
+            return Err(OreError::TooManyMines);
+        }
+    }
+
+    // An earlier instruction must authenticate the proof.
+    if mine_policy & MINE_POLICY_REQUIRE_AUTH != 0
+        && !instructions[..current_index]
+            .iter()
+            .any(|ix| ix.program_id.eq(&NOOP_PROGRAM_ID) && ix.data.eq(&proof.to_bytes()))
+    {
+        return Err(OreError::MissingAuth);
     }
-    .log_return();
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::prelude::*;
+    use steel::*;
+
+    use crate::mine::{check_mine_policy, split_mine_accounts, MineAccounts};
+
+    fn args(coal_boost_accounts: u8, ore_boost_accounts: u8) -> Mine {
+        Mine {
+            digest: [0; 16],
+            nonce: [0; 8],
+            coal_boost_accounts,
+            ore_boost_accounts,
+        }
+    }
+
+    #[test]
+    fn test_split_mine_accounts() {
+        let accounts: Vec<usize> = (0..20).collect();
+        for (len, coal_boost_accounts, ore_boost_accounts) in
+            [(15, 0, 0), (18, 0, 3), (17, 2, 0), (20, 2, 3)]
+        {
+            let split =
+                split_mine_accounts(&accounts[..len], &args(coal_boost_accounts, ore_boost_accounts))
+                    .unwrap();
+            let ore_boosts_end = 15 + ore_boost_accounts as usize;
+            assert_eq!(
+                split,
+                MineAccounts {
+                    required: &accounts[..15],
+                    ore_boosts: &accounts[15..ore_boosts_end],
+                    coal_boosts: &accounts[ore_boosts_end..len],
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_mine_accounts_too_few() {
+        let accounts: Vec<usize> = (0..20).collect();
+        for (len, coal_boost_accounts, ore_boost_accounts) in
+            [(0, 0, 0), (14, 0, 0), (15, 0, 3), (17, 0, 3), (15, 2, 0), (18, 2, 3), (19, 2, 3)]
+        {
+            assert_eq!(
+                split_mine_accounts(&accounts[..len], &args(coal_boost_accounts, ore_boost_accounts)),
+                Err(ProgramError::NotEnoughAccountKeys)
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_mine_accounts_invalid() {
+        let accounts: Vec<usize> = (0..22).collect();
+        let err = ProgramError::from(OreError::InvalidMineAccounts);
+
+        // Accounts past the counts in the header.
+        for (len, coal_boost_accounts, ore_boost_accounts) in
+            [(16, 0, 0), (18, 0, 0), (19, 0, 3), (18, 2, 0), (22, 2, 3)]
+        {
+            assert_eq!(
+                split_mine_accounts(&accounts[..len], &args(coal_boost_accounts, ore_boost_accounts)),
+                Err(err.clone())
+            );
+        }
+
+        // ORE boosts come in groups of 3 accounts.
+        for ore_boost_accounts in [1, 2, 4, 6, u8::MAX] {
+            assert_eq!(
+                split_mine_accounts(&accounts, &args(0, ore_boost_accounts)),
+                Err(err.clone())
+            );
+        }
+
+        // COAL boosts come in pairs of accounts, up to the max boosts.
+        for coal_boost_accounts in [1, 3, 8, u8::MAX] {
+            assert_eq!(
+                split_mine_accounts(&accounts, &args(coal_boost_accounts, 0)),
+                Err(err.clone())
+            );
+        }
+    }
+
+
+    fn mine_ix(program_id: Pubkey) -> Instruction {
+        let mut ix = coal_api::sdk::mine(
+            MINT_ADDRESS,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            bus_pda(MINT_ADDRESS, 0).0,
+            ore_api::consts::BUS_ADDRESSES[0],
+            drillx::Solution::new([0; 16], [0; 8]),
+            vec![],
+            None,
+        );
+        ix.program_id = program_id;
+        ix
+    }
+
+    #[test]
+    fn test_check_mine_policy_single_mine() {
+        let proof = Pubkey::new_unique();
+        let one = [coal_api::sdk::auth(proof), mine_ix(coal_api::ID)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_SINGLE_MINE, &[], &proof, &one, 1),
+            Ok(())
+        );
+        let two = [mine_ix(coal_api::ID), mine_ix(coal_api::ID)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_SINGLE_MINE, &[], &proof, &two, 0),
+            Err(OreError::TooManyMines)
+        );
+
+        // Pool mines and submissions count as mines too.
+        let mut pool_mine = mine_ix(coal_api::ID);
+        pool_mine.data[0] = OreInstruction::MinePool as u8;
+        let mut submission = mine_ix(coal_api::ID);
+        submission.data[0] = OreInstruction::SubmitPool as u8;
+        for ixs in [
+            [mine_ix(coal_api::ID), pool_mine.clone()],
+            [pool_mine.clone(), pool_mine],
+            [submission, mine_ix(coal_api::ID)],
+        ] {
+            assert_eq!(
+                check_mine_policy(MINE_POLICY_SINGLE_MINE, &[], &proof, &ixs, 0),
+                Err(OreError::TooManyMines)
+            );
+        }
+
+        // Without the flag, several mines are allowed.
+        assert_eq!(check_mine_policy(0, &[], &proof, &two, 1), Ok(()));
+    }
+
+    #[test]
+    fn test_check_mine_policy_require_auth() {
+        let proof = Pubkey::new_unique();
+        let ixs = [coal_api::sdk::auth(proof), mine_ix(coal_api::ID)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_REQUIRE_AUTH, &[], &proof, &ixs, 1),
+            Ok(())
+        );
+
+        // The auth must come before the mine and carry the proof address.
+        let after = [mine_ix(coal_api::ID), coal_api::sdk::auth(proof)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_REQUIRE_AUTH, &[], &proof, &after, 0),
+            Err(OreError::MissingAuth)
+        );
+        let other = [coal_api::sdk::auth(Pubkey::new_unique()), mine_ix(coal_api::ID)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_REQUIRE_AUTH, &[], &proof, &other, 1),
+            Err(OreError::MissingAuth)
+        );
+    }
+
+    #[test]
+    fn test_check_mine_policy_cpi_allowlist() {
+        let proof = Pubkey::new_unique();
+        let allowed = Pubkey::new_unique();
+        let allowlist = [allowed];
+        let direct = [mine_ix(coal_api::ID)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_CPI_ALLOWLIST, &allowlist, &proof, &direct, 0),
+            Ok(())
+        );
+        let cpi = [mine_ix(allowed)];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_CPI_ALLOWLIST, &allowlist, &proof, &cpi, 0),
+            Ok(())
+        );
+        let unknown = [mine_ix(Pubkey::new_unique())];
+        assert_eq!(
+            check_mine_policy(MINE_POLICY_CPI_ALLOWLIST, &allowlist, &proof, &unknown, 0),
+            Err(OreError::CpiNotAllowed)
+        );
+    }
+}
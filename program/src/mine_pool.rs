@@ -0,0 +1,48 @@
+use coal_api::prelude::*;
+use steel::*;
+
+use crate::mine::mine;
+
+/// MinePool submits a hash for a pool's proof and credits the member who found it with the share
+/// and the whole reward.
+///
+/// Pool programs call this by CPI with their authority PDA as the signer. Such calls must come
+/// from a program on the config's CPI allowlist, whether or not the mine policy requires it.
+pub fn process_mine_pool(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = MinePool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [pool_info, member_info, mine_accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let [signer_info, _, _, _, proof_info, ..] = mine_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let pool = pool_info
+        .is_writable()?
+        .as_account_mut::<Pool>(&coal_api::ID)?
+        .assert_mut_err(
+            |p| p.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert_mut_err(|p| p.proof == *proof_info.key, OreError::InvalidPool.into())?;
+    let member = member_info
+        .is_writable()?
+        .as_account_mut::<PoolMember>(&coal_api::ID)?
+        .assert_mut_err(|m| m.pool == *pool_info.key, OreError::InvalidPoolMember.into())?;
+
+    // Mine with the pool's proof.
+    let event = mine(mine_accounts, &args.mine(), MINE_POLICY_CPI_ALLOWLIST)?;
+
+    // Record the member's share.
+    member.record_share(event.difficulty);
+    member.credit(event.reward);
+    pool.total_hashes = pool.total_hashes.saturating_add(1);
+    pool.total_difficulty = pool.total_difficulty.saturating_add(event.difficulty);
+
+    // Log data.
+    event.log_return();
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// NewBoost creates a boost for stakers of a token, and the token account holding the stake.
+pub fn process_new_boost(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = NewBoost::try_from_bytes(data)?;
+    let multiplier = u64::from_le_bytes(args.multiplier);
+    let expires_at = i64::from_le_bytes(args.expires_at);
+
+    // Load accounts.
+    let [signer_info, boost_info, boost_tokens_info, mint_info, system_program, token_program, associated_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?.has_address(&INITIALIZER_ADDRESS)?;
+    boost_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[BOOST, mint_info.key.as_ref()], &coal_api::ID)?;
+    boost_tokens_info.is_empty()?.is_writable()?;
+    mint_info.as_mint_interface()?;
+    system_program.is_program(&system_program::ID)?;
+    token_program.is_token_program()?;
+    associated_token_program.is_program(&spl_associated_token_account::ID)?;
+
+    // Initialize boost.
+    let (_, boost_bump) = boost_pda(*mint_info.key);
+    create_account::<Boost>(
+        boost_info,
+        system_program,
+        signer_info,
+        &coal_api::ID,
+        &[BOOST, mint_info.key.as_ref()],
+    )?;
+    let boost = boost_info.as_account_mut::<Boost>(&coal_api::ID)?;
+    boost.mint = *mint_info.key;
+    boost.multiplier = multiplier;
+    boost.expires_at = expires_at;
+    boost.total_stake = 0;
+    boost.bump = boost_bump as u64;
+
+    // Initialize the token account holding the stake.
+    create_associated_token_account_interface(
+        signer_info,
+        boost_info,
+        boost_tokens_info,
+        mint_info,
+        system_program,
+        token_program,
+        associated_token_program,
+    )?;
+
+    Ok(())
+}
@@ -1,6 +1,4 @@
 use coal_api::prelude::*;
-use ore_api;
-use ore_boost_api;
 use steel::*;
 
 /// Open creates a new proof account to track a miner's state.
@@ -9,13 +7,15 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
     let args = Open::try_from_bytes(data)?;
 
     // Load accounts.
-    let [signer_info, config_info, miner_info, payer_info, proof_info, ore_proof_info, ore_reservation_into, mint_info, system_program, slot_hashes_info] =
+    let [signer_info, config_info, miner_info, payer_info, proof_info, ore_proof_info, ore_reservation_into, mint_info, system_program, slot_hashes_info, ore_program, ore_boost_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
-    config_info.is_config()?
+    config_info
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
         .as_account::<Config>(&coal_api::ID)?
         .assert(|c| c.mint == *mint_info.key)?;
     payer_info.is_signer()?;
@@ -23,27 +23,62 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         .is_empty()?
         .is_writable()?
         .has_seeds(&[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref()], &coal_api::ID)?;
-    mint_info.as_mint()?;
+    mint_info.as_mint_interface()?;
     system_program.is_program(&system_program::ID)?;
     slot_hashes_info.is_sysvar(&sysvar::slot_hashes::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    ore_boost_program.is_program(&ore_boost_api::ID)?;
 
     // Initialize proof.
+    open_proof(
+        signer_info.key,
+        miner_info.key,
+        args.proof_bump,
+        payer_info,
+        proof_info,
+        ore_proof_info,
+        ore_reservation_into,
+        mint_info,
+        system_program,
+        slot_hashes_info,
+        ore_program,
+        ore_boost_program,
+    )
+}
+
+/// Creates a proof for the authority, and opens and registers its ORE proof.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_proof<'info>(
+    authority: &Pubkey,
+    miner: &Pubkey,
+    proof_bump: u8,
+    payer_info: &AccountInfo<'info>,
+    proof_info: &AccountInfo<'info>,
+    ore_proof_info: &AccountInfo<'info>,
+    ore_reservation_into: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    slot_hashes_info: &AccountInfo<'info>,
+    ore_program: &AccountInfo<'info>,
+    ore_boost_program: &AccountInfo<'info>,
+) -> ProgramResult {
     create_account::<Proof>(
         proof_info,
         system_program,
         payer_info,
         &coal_api::ID,
-        &[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref()],
+        &[PROOF, mint_info.key.as_ref(), authority.as_ref()],
     )?;
     let clock = Clock::get()?;
     let proof = proof_info.as_account_mut::<Proof>(&coal_api::ID)?;
-    proof.authority = *signer_info.key;
+    proof.authority = *authority;
     proof.balance = 0;
     proof.last_stake_at = clock.unix_timestamp;
-    proof.miner = *miner_info.key;
+    proof.miner = *miner;
     proof.total_hashes = 0;
     proof.total_rewards = 0;
-    proof.bump = args.proof_bump as u64;
+    proof.bump = proof_bump as u64;
+    proof.last_mine_slot = 0;
 
 
     let open_accounts = &[
@@ -52,7 +87,8 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         payer_info.clone(),
         ore_proof_info.clone(),
         system_program.clone(),
-        slot_hashes_info.clone()
+        slot_hashes_info.clone(),
+        ore_program.clone(),
     ];
 
     solana_program::program::invoke_signed(
@@ -63,7 +99,7 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
             *payer_info.key,
         ),
         open_accounts,
-        &[&[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref(), &[args.proof_bump]]]
+        &[&[PROOF, mint_info.key.as_ref(), authority.as_ref(), &[proof_bump]]]
     )?;
 
 
@@ -73,6 +109,7 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
         ore_proof_info.clone(),
         ore_reservation_into.clone(),
         system_program.clone(),
+        ore_boost_program.clone(),
     ];
     // Register the proof with the boost program
     solana_program::program::invoke_signed(
@@ -82,7 +119,7 @@ pub fn process_open(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult
             *ore_proof_info.key
         ),
         register_accounts,
-        &[&[PROOF, mint_info.key.as_ref(), signer_info.key.as_ref(), &[args.proof_bump]]]
+        &[&[PROOF, mint_info.key.as_ref(), authority.as_ref(), &[proof_bump]]]
     )?;
 
     Ok(())
@@ -0,0 +1,70 @@
+use coal_api::prelude::*;
+use steel::*;
+
+use crate::open::open_proof;
+
+/// OpenPool creates a pool and the proof it mines with, owned by the pool.
+pub fn process_open_pool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = OpenPool::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, config_info, payer_info, pool_info, proof_info, ore_proof_info, ore_reservation_info, mint_info, system_program, slot_hashes_info, ore_program, ore_boost_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    config_info
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account::<Config>(&coal_api::ID)?
+        .assert(|c| c.mint == *mint_info.key)?;
+    payer_info.is_signer()?;
+    pool_info.is_empty()?.is_writable()?.has_seeds(
+        &[POOL, mint_info.key.as_ref(), signer_info.key.as_ref()],
+        &coal_api::ID,
+    )?;
+    proof_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[PROOF, mint_info.key.as_ref(), pool_info.key.as_ref()], &coal_api::ID)?;
+    mint_info.as_mint_interface()?;
+    system_program.is_program(&system_program::ID)?;
+    slot_hashes_info.is_sysvar(&sysvar::slot_hashes::ID)?;
+    ore_program.is_program(&ore_api::ID)?;
+    ore_boost_program.is_program(&ore_boost_api::ID)?;
+
+    // Initialize pool.
+    let (_, pool_bump) = pool_pda(*mint_info.key, *signer_info.key);
+    create_account::<Pool>(
+        pool_info,
+        system_program,
+        payer_info,
+        &coal_api::ID,
+        &[POOL, mint_info.key.as_ref(), signer_info.key.as_ref()],
+    )?;
+    let pool = pool_info.as_account_mut::<Pool>(&coal_api::ID)?;
+    pool.authority = *signer_info.key;
+    pool.proof = *proof_info.key;
+    pool.total_members = 0;
+    pool.total_hashes = 0;
+    pool.total_difficulty = 0;
+    pool.bump = pool_bump as u64;
+
+    // Initialize the pool's proof. The pool authority submits hashes for it.
+    open_proof(
+        pool_info.key,
+        signer_info.key,
+        args.proof_bump,
+        payer_info,
+        proof_info,
+        ore_proof_info,
+        ore_reservation_info,
+        mint_info,
+        system_program,
+        slot_hashes_info,
+        ore_program,
+        ore_boost_program,
+    )
+}
@@ -0,0 +1,38 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// OpenStake creates an account to track a user's stake in a boost.
+pub fn process_open_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let _args = OpenStake::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer_info, boost_info, stake_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    boost_info.as_account::<Boost>(&coal_api::ID)?;
+    stake_info.is_empty()?.is_writable()?.has_seeds(
+        &[STAKE, boost_info.key.as_ref(), signer_info.key.as_ref()],
+        &coal_api::ID,
+    )?;
+    system_program.is_program(&system_program::ID)?;
+
+    // Initialize stake.
+    let (_, stake_bump) = stake_pda(*boost_info.key, *signer_info.key);
+    create_account::<Stake>(
+        stake_info,
+        system_program,
+        signer_info,
+        &coal_api::ID,
+        &[STAKE, boost_info.key.as_ref(), signer_info.key.as_ref()],
+    )?;
+    let stake = stake_info.as_account_mut::<Stake>(&coal_api::ID)?;
+    stake.authority = *signer_info.key;
+    stake.boost = *boost_info.key;
+    stake.balance = 0;
+    stake.last_deposit_at = 0;
+    stake.bump = stake_bump as u64;
+
+    Ok(())
+}
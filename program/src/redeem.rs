@@ -0,0 +1,122 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// Redeem burns COAL in exchange for a pro-rata share of the treasury ORE backing its mint.
+pub fn process_redeem(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = Redeem::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer_info, beneficiary_info, config_info, mint_info, sender_info, treasury_info, treasury_ore_tokens_info, token_program, ore_token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    beneficiary_info
+        .is_writable()?
+        .as_token_account()?
+        .assert(|t| t.mint == ORE_MINT_ADDRESS)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+    let mint = mint_info
+        .is_writable()?
+        .has_address(&config.mint)?
+        .as_mint_interface()?;
+    sender_info
+        .is_writable()?
+        .as_token_account_interface()?
+        .assert(|t| t.owner == *signer_info.key)?
+        .assert(|t| t.mint == config.mint)?;
+    treasury_info.is_treasury()?;
+    let treasury_ore_tokens = treasury_ore_tokens_info
+        .is_writable()?
+        .as_associated_token_account(treasury_info.key, &ORE_MINT_ADDRESS)?;
+    token_program.is_token_program()?;
+    ore_token_program.is_program(&spl_token::ID)?;
+
+    // Calculate the redeemer's share of the ORE backing this mint.
+    let ore_balance = config.ore_balance.min(treasury_ore_tokens.amount);
+    let ore_amount = calculate_redeem_amount(amount, mint.supply, ore_balance);
+    if ore_amount == 0 {
+        return Err(OreError::RedeemTooSmall.into());
+    }
+
+    // Burn the redeemed COAL.
+    burn_interface(sender_info, mint_info, signer_info, token_program, amount)?;
+
+    // Transfer ORE from the treasury to the beneficiary.
+    transfer_signed(
+        treasury_info,
+        treasury_ore_tokens_info,
+        beneficiary_info,
+        ore_token_program,
+        ore_amount,
+        &[TREASURY],
+    )?;
+
+    // Update the mint's backing and lifetime stats.
+    config.ore_balance = config.ore_balance.saturating_sub(ore_amount);
+    config.total_redeemed = config.total_redeemed.saturating_add(amount);
+    config.total_ore_redeemed = config.total_ore_redeemed.saturating_add(ore_amount);
+    config.total_burned = config.total_burned.saturating_add(amount);
+
+    // Log data.
+    RedeemEvent { amount, ore_amount }.log();
+
+    Ok(())
+}
+
+/// Calculates the quantity of ORE owed for burning `amount` COAL out of the current supply, given
+/// the treasury ORE backing the mint.
+///
+/// ore_amount = treasury_ore * (amount / supply)
+///
+/// The result is rounded down so the treasury can never pay out more than its balance.
+pub(crate) fn calculate_redeem_amount(amount: u64, supply: u64, treasury_ore: u64) -> u64 {
+    if supply.eq(&0) {
+        return 0;
+    }
+    (treasury_ore as u128)
+        .saturating_mul(amount.min(supply) as u128)
+        .saturating_div(supply as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculate_redeem_amount;
+
+    #[test]
+    fn test_calculate_redeem_amount_pro_rata() {
+        let ore_amount = calculate_redeem_amount(250, 1_000, 4_000);
+        assert!(ore_amount.eq(&1_000));
+    }
+
+    #[test]
+    fn test_calculate_redeem_amount_full_supply() {
+        let ore_amount = calculate_redeem_amount(1_000, 1_000, 4_000);
+        assert!(ore_amount.eq(&4_000));
+    }
+
+    #[test]
+    fn test_calculate_redeem_amount_rounds_down() {
+        let ore_amount = calculate_redeem_amount(1, 3, 2);
+        assert!(ore_amount.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_redeem_amount_zero_supply() {
+        let ore_amount = calculate_redeem_amount(1_000, 0, 4_000);
+        assert!(ore_amount.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_redeem_amount_max_inputs() {
+        let ore_amount = calculate_redeem_amount(u64::MAX, u64::MAX, u64::MAX);
+        assert!(ore_amount.eq(&u64::MAX));
+    }
+}
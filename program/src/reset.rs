@@ -1,13 +1,15 @@
-use std::ops::Mul;
-
 use coal_api::prelude::*;
 use steel::*;
 
 /// Reset tops up the bus balances, updates the base reward rate, and sets up the ORE program for the next epoch.
-pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
-    // Load accounts.
+pub fn process_reset(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args. Older clients send no args, and get the non-strict behavior.
+    let strict = !data.is_empty() && Reset::try_from_bytes(data)?.strict.eq(&1);
+
+    // Load accounts. The token account receiving the crank reward is optional.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(14));
     let [signer_info, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
-        accounts
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -53,221 +55,73 @@ pub fn process_reset(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResul
         .as_account_mut::<Bus>(&coal_api::ID)?
         .assert_mut(|b| b.id == 7)?;
     let config = config_info
-        .is_config()?
         .is_writable()?
-        .has_seeds(&[&CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
         .as_account_mut::<Config>(&coal_api::ID)?;
     let mint = mint_info
         .has_address(&config.mint)?
         .is_writable()?
-        .as_mint()?;
+        .as_mint_interface()?;
     treasury_info.is_treasury()?.is_writable()?;
-    treasury_tokens_info.is_treasury_tokens()?.is_writable()?;
-    token_program.is_program(&spl_token::ID)?;
+    treasury_tokens_info
+        .is_writable()?
+        .as_associated_token_account_interface(treasury_info.key, mint_info.key)?;
+    token_program.is_token_program()?;
+    let crank_reward_tokens_info = optional_accounts.first();
+    if let Some(crank_reward_tokens_info) = crank_reward_tokens_info {
+        crank_reward_tokens_info
+            .is_writable()?
+            .as_token_account_interface()?
+            .assert(|t| t.mint == *mint_info.key)?;
+    }
 
     // Validate enough time has passed since the last reset.
     let clock = Clock::get()?;
-    if config
-        .last_reset_at
-        .saturating_add(EPOCH_DURATION)
-        .gt(&clock.unix_timestamp)
-    {
+    if next_reset_at(config).gt(&clock.unix_timestamp) {
+        if strict {
+            return Err(OreError::ResetTooEarly.into());
+        }
         return Ok(());
     }
 
     // Update timestamp.
     config.last_reset_at = clock.unix_timestamp;
-    config.current_epoch = config.current_epoch.saturating_add(1);
-    
-    // Reset bus accounts and calculate actual rewards mined since last reset.
-    let target_epoch_rewards = config.get_epoch_rewards();
-    let busses = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
-    let mut total_remaining_rewards = 0u64;
-    let mut total_theoretical_rewards = 0u64;
-
-    for bus in busses {
-        // Track accumulators.
-        total_remaining_rewards = total_remaining_rewards.saturating_add(bus.rewards);
-        total_theoretical_rewards =
-            total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
-
-        // Reset bus account for new epoch.
-        bus.rewards = config.get_epoch_rewards();
-        bus.theoretical_rewards = 0;
-    }
-    let max_epoch_rewards = target_epoch_rewards.mul(8);
-    let total_epoch_rewards = max_epoch_rewards.saturating_sub(total_remaining_rewards);
-
-    // Update base reward rate for next epoch.
-    config.base_reward_rate =
-        calculate_new_reward_rate(config.base_reward_rate, total_theoretical_rewards);
-
-    // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
-    let base_reward_rate_min_threshold = BASE_REWARD_RATE_MIN_THRESHOLD.saturating_mul(target_epoch_rewards).saturating_div(ONE_ORE);
-    
-    if config.base_reward_rate.le(&base_reward_rate_min_threshold) {
-        config.min_difficulty = config.min_difficulty.checked_add(1).unwrap();
-        config.base_reward_rate = config.base_reward_rate.checked_mul(2).unwrap();
-    }
-
-    // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
-    let base_reward_rate_max_threshold = BASE_REWARD_RATE_MAX_THRESHOLD.saturating_mul(target_epoch_rewards).saturating_div(ONE_ORE);
-    
-    if config.base_reward_rate.ge(&base_reward_rate_max_threshold) && config.min_difficulty.gt(&1) {
-        config.min_difficulty = config.min_difficulty.checked_sub(1).unwrap();
-        config.base_reward_rate = config.base_reward_rate.checked_div(2).unwrap();
-    }
 
-    // Max supply check.
-    if mint.supply.ge(&config.max_supply) {
-        return Err(OreError::MaxSupply.into());
+    // Roll the config and busses over to the next epoch.
+    let bus_accounts = [bus_0, bus_1, bus_2, bus_3, bus_4, bus_5, bus_6, bus_7];
+    let mut busses = bus_accounts.each_ref().map(|bus| **bus);
+    let amounts = apply_reset(config, &mut busses, mint.supply)?;
+    for (bus_account, bus) in bus_accounts.into_iter().zip(busses) {
+        *bus_account = bus;
     }
 
     // Fund the treasury token account.
-    let amount = config.max_supply
-        .saturating_sub(mint.supply)
-        .min(total_epoch_rewards);
-    
-    mint_to_signed(
+    mint_to_interface_signed(
         mint_info,
         treasury_tokens_info,
         treasury_info,
         token_program,
-        amount,
+        amounts.treasury,
         &[TREASURY],
     )?;
 
-    Ok(())
-}
-
-/// This function calculates what the new reward rate should be based on how many total rewards
-/// were mined in the prior epoch. The math is largely identitical to function used by the Bitcoin
-/// network to update the difficulty between each epoch.
-///
-/// new_rate = current_rate * (target_rewards / actual_rewards)
-///
-/// The new rate is then smoothed by a constant factor to avoid large fluctuations. In Ore's case,
-/// the epochs are short (60 seconds) so a smoothing factor of 2 has been chosen. That is, the reward rate
-/// can at most double or halve from one epoch to the next.
-pub(crate) fn calculate_new_reward_rate(current_rate: u64, epoch_rewards: u64) -> u64 {
-    // Avoid division by zero. Leave the reward rate unchanged, if detected.
-    if epoch_rewards.eq(&0) {
-        return current_rate;
-    }
-
-    // Calculate new reward rate.
-    let new_rate = (current_rate as u128)
-        .saturating_mul(TARGET_EPOCH_REWARDS as u128)
-        .saturating_div(epoch_rewards as u128) as u64;
-
-    // Smooth reward rate so it cannot change by more than a constant factor from one epoch to the next.
-    let new_rate_min = current_rate.saturating_div(SMOOTHING_FACTOR);
-    let new_rate_max = current_rate.saturating_mul(SMOOTHING_FACTOR);
-    let new_rate_smoothed = new_rate.min(new_rate_max).max(new_rate_min);
-
-    // Prevent reward rate from dropping below 1 or exceeding BUS_EPOCH_REWARDS and return.
-    new_rate_smoothed.max(1).min(BUS_EPOCH_REWARDS)
-}
-
-#[cfg(test)]
-mod tests {
-    use rand::{distributions::Uniform, Rng};
-
-    use crate::calculate_new_reward_rate;
-    use coal_api::consts::{
-        BASE_REWARD_RATE_MIN_THRESHOLD, BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS, SMOOTHING_FACTOR,
-        TARGET_EPOCH_REWARDS,
-    };
-
-    const FUZZ_SIZE: u64 = 10_000;
-
-    #[test]
-    fn test_calculate_new_reward_rate_target() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS);
-        assert!(new_rate.eq(&current_rate));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_div_by_zero() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 0);
-        assert!(new_rate.eq(&current_rate));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_lower() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(
-            current_rate,
-            TARGET_EPOCH_REWARDS.saturating_add(1_000_000_000),
-        );
-        assert!(new_rate.lt(&current_rate));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_lower_edge() {
-        let current_rate = BASE_REWARD_RATE_MIN_THRESHOLD;
-        let new_rate = calculate_new_reward_rate(current_rate, TARGET_EPOCH_REWARDS + 1);
-        assert!(new_rate.lt(&current_rate));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_lower_fuzz() {
-        let mut rng = rand::thread_rng();
-        for _ in 0..FUZZ_SIZE {
-            let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
-            let actual_rewards: u64 =
-                rng.sample(Uniform::new(TARGET_EPOCH_REWARDS, MAX_EPOCH_REWARDS));
-            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
-            assert!(new_rate.lt(&current_rate));
+    // Track the treasury balance.
+    config.total_balance = config.total_balance.saturating_add(amounts.treasury);
+
+    // Pay the crank reward. Only the first reset of the epoch gets this far.
+    if let Some(crank_reward_tokens_info) = crank_reward_tokens_info {
+        if amounts.crank.gt(&0) {
+            mint_to_interface_signed(
+                mint_info,
+                crank_reward_tokens_info,
+                treasury_info,
+                token_program,
+                amounts.crank,
+                &[TREASURY],
+            )?;
         }
     }
 
-    #[test]
-    fn test_calculate_new_reward_rate_higher() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(
-            current_rate,
-            TARGET_EPOCH_REWARDS.saturating_sub(1_000_000_000),
-        );
-        assert!(new_rate.gt(&current_rate));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_higher_fuzz() {
-        let mut rng = rand::thread_rng();
-        for _ in 0..FUZZ_SIZE {
-            let current_rate: u64 = rng.sample(Uniform::new(1, BUS_EPOCH_REWARDS));
-            let actual_rewards: u64 = rng.sample(Uniform::new(1, TARGET_EPOCH_REWARDS));
-            let new_rate = calculate_new_reward_rate(current_rate, actual_rewards);
-            assert!(new_rate.gt(&current_rate));
-        }
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_max_smooth() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, 1);
-        assert!(new_rate.eq(&current_rate.saturating_mul(SMOOTHING_FACTOR)));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_min_smooth() {
-        let current_rate = 1000;
-        let new_rate = calculate_new_reward_rate(current_rate, u64::MAX);
-        assert!(new_rate.eq(&current_rate.saturating_div(SMOOTHING_FACTOR)));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_max_inputs() {
-        let new_rate = calculate_new_reward_rate(BUS_EPOCH_REWARDS, MAX_EPOCH_REWARDS);
-        assert!(new_rate.eq(&BUS_EPOCH_REWARDS.saturating_div(SMOOTHING_FACTOR)));
-    }
-
-    #[test]
-    fn test_calculate_new_reward_rate_min_inputs() {
-        let new_rate = calculate_new_reward_rate(1, 1);
-        assert!(new_rate.eq(&1u64.saturating_mul(SMOOTHING_FACTOR)));
-    }
+    Ok(())
 }
@@ -0,0 +1,28 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// SetMigration opens, updates, or closes the migration of a legacy token into a mint.
+pub fn process_set_migration(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetMigration::try_from_bytes(data)?;
+    let migration_ratio = u64::from_le_bytes(args.migration_ratio);
+    let migration_deadline = u64::from_le_bytes(args.migration_deadline);
+
+    // Load accounts.
+    let [signer_info, config_info, mint_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?.has_address(&INITIALIZER_ADDRESS)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+
+    // Update the migration settings.
+    config.migration_mint = args.migration_mint;
+    config.migration_ratio = migration_ratio;
+    config.migration_deadline = migration_deadline;
+
+    Ok(())
+}
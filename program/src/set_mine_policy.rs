@@ -0,0 +1,26 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// SetMinePolicy updates the anti-cheat checks applied to mine instructions.
+pub fn process_set_mine_policy(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SetMinePolicy::try_from_bytes(data)?;
+    let mine_policy = u64::from_le_bytes(args.mine_policy);
+
+    // Load accounts.
+    let [signer_info, config_info, mint_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?.has_address(&INITIALIZER_ADDRESS)?;
+    let config = config_info
+        .is_writable()?
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
+        .as_account_mut::<Config>(&coal_api::ID)?;
+
+    // Update the mine policy.
+    config.mine_policy = mine_policy;
+    config.mine_cpi_allowlist = args.mine_cpi_allowlist;
+
+    Ok(())
+}
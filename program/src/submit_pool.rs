@@ -0,0 +1,111 @@
+use coal_api::prelude::*;
+use drillx::Solution;
+use ore_api::state::proof_pda as ore_proof_pda;
+use steel::*;
+
+use crate::mine::mine;
+
+/// SubmitPool verifies the best solution of each member on the pool's challenge, mines with the
+/// best of them, and splits the reward between the members proportionally to their difficulty.
+///
+/// Like MinePool, calls by CPI must come from a program on the config's CPI allowlist.
+pub fn process_submit_pool(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SubmitPool::try_from_bytes(data)?;
+    let members = args.members as usize;
+    if members.eq(&0) || members.gt(&MAX_POOL_SUBMISSIONS) {
+        return Err(OreError::InvalidPoolSubmission.into());
+    }
+
+    // Load accounts.
+    let [pool_info, accounts @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let (mine_accounts, member_infos) = accounts.split_at(
+        accounts
+            .len()
+            .checked_sub(members)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?,
+    );
+    let [signer_info, _, _, config_info, proof_info, _, _, ore_proof_info, ..] = mine_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let min_difficulty = config_info
+        .has_current_layout::<Config>()?
+        .as_account::<Config>(&coal_api::ID)?
+        .min_difficulty;
+    let pool = pool_info
+        .is_writable()?
+        .as_account_mut::<Pool>(&coal_api::ID)?
+        .assert_mut_err(
+            |p| p.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert_mut_err(|p| p.proof == *proof_info.key, OreError::InvalidPool.into())?;
+    let challenge = ore_proof_info
+        .has_address_err(&ore_proof_pda(*proof_info.key).0, OreError::InvalidOreAccounts)?
+        .as_account::<ore_api::state::Proof>(&ore_api::ID)?
+        .challenge;
+
+    // Each member and solution must be distinct, and each solution found in the member's own nonce
+    // range, so members cannot submit each other's work.
+    for (i, member_info) in member_infos.iter().enumerate() {
+        let member = member_info
+            .is_writable()?
+            .as_account::<PoolMember>(&coal_api::ID)?
+            .assert(|m| m.pool == *pool_info.key)
+            .map_err(|_| OreError::InvalidPoolMember)?;
+        if member_infos[..i].iter().any(|m| m.key.eq(member_info.key))
+            || args.solutions[..i].contains(&args.solutions[i])
+        {
+            return Err(OreError::InvalidPoolSubmission.into());
+        }
+        if !member.owns_nonce(Solution::from_bytes(args.solutions[i]).n) {
+            return Err(OreError::InvalidPoolNonce.into());
+        }
+    }
+
+    // Verify the solution of each member.
+    let mut difficulties = Vec::with_capacity(members);
+    let (mut best, mut best_difficulty) = (0, 0);
+    for i in 0..members {
+        let solution = Solution::from_bytes(args.solutions[i]);
+        if !solution.is_valid(&challenge) {
+            return Err(OreError::HashInvalid.into());
+        }
+        let difficulty = solution.to_hash().difficulty() as u64;
+        if difficulty.lt(&min_difficulty) {
+            return Err(OreError::HashTooEasy.into());
+        }
+        if difficulty.gt(&best_difficulty) {
+            (best, best_difficulty) = (i, difficulty);
+        }
+        difficulties.push(difficulty);
+    }
+
+    // Mine with the best solution on the pool's proof.
+    let solution = Solution::from_bytes(args.solutions[best]);
+    let mine_args = Mine {
+        digest: solution.d,
+        nonce: solution.n,
+        coal_boost_accounts: args.coal_boost_accounts,
+        ore_boost_accounts: args.ore_boost_accounts,
+    };
+    let event = mine(mine_accounts, &mine_args, MINE_POLICY_CPI_ALLOWLIST)?;
+
+    // Split the reward between the members.
+    let shares = split_pool_reward(event.reward, &difficulties);
+    for ((member_info, difficulty), share) in member_infos.iter().zip(difficulties).zip(shares) {
+        let member = member_info.as_account_mut::<PoolMember>(&coal_api::ID)?;
+        member.record_share(difficulty);
+        member.credit(share);
+        pool.total_hashes = pool.total_hashes.saturating_add(1);
+        pool.total_difficulty = pool.total_difficulty.saturating_add(difficulty);
+    }
+
+    // Log data.
+    event.log_return();
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ pub fn process_update(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResu
     };
     signer_info.is_signer()?;
     let proof = proof_info
+        .has_current_layout::<Proof>()?
         .as_account_mut::<Proof>(&coal_api::ID)?
         .assert_mut_err(
             |p| p.authority == *signer_info.key,
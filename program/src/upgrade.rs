@@ -1,74 +1,84 @@
 use coal_api::prelude::*;
 use steel::*;
 
-/// Upgrade allows a user to migrate a v1 token to a v2 token at a 1:1 exchange rate.
+/// Upgrade allows a user to migrate a legacy token to a v2 token at the config's exchange rate.
 pub fn process_upgrade(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args
     let args = Upgrade::try_from_bytes(data)?;
     let amount = u64::from_le_bytes(args.amount);
 
     // Load accounts
-    let [signer_info, config_info, beneficiary_info, mint_info, mint_v1_info, sender_info, treasury_info, token_program] =
+    let [signer_info, config_info, beneficiary_info, mint_info, mint_v1_info, sender_info, treasury_info, token_program, migration_token_program] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     signer_info.is_signer()?;
     let config = config_info
+        .has_seeds(&[CONFIG, mint_info.key.as_ref()], &coal_api::ID)?
+        .has_current_layout::<Config>()?
         .as_account::<Config>(&coal_api::ID)?
-        .assert(|c| c.mint == *mint_info.key)?;
+        .assert(|c| c.mint == *mint_info.key)?
+        .assert_err(|c| c.is_migration_open(), OreError::MigrationClosed.into())?;
     beneficiary_info
         .is_writable()?
-        .as_token_account()?
+        .as_token_account_interface()?
         .assert(|t| t.owner == *signer_info.key)?;
     let mint = mint_info
         .is_writable()?
         .has_address(&config.mint)?
-        .as_mint()?;
-    mint_v1_info
+        .as_mint_interface()?;
+    let mint_v1 = mint_v1_info
         .is_writable()?
         .has_address(&config.migration_mint)?
-        .as_mint()?;
+        .as_mint_interface()?;
     sender_info
         .is_writable()?
-        .as_token_account()?
+        .as_token_account_interface()?
         .assert(|t| t.owner == *signer_info.key)?
         .assert(|t| t.mint == config.migration_mint)?;
     treasury_info.is_treasury()?;
-    token_program.is_program(&spl_token::ID)?;
+    token_program.is_token_program()?;
+    migration_token_program.is_token_program()?;
+
+    // Convert to the v2 token at the migration ratio.
+    let upgrade_amount = calculate_upgrade_amount(
+        amount,
+        mint_v1.decimals,
+        mint.decimals,
+        config.migration_ratio,
+    );
+    if upgrade_amount == 0 {
+        return Err(OreError::UpgradeTooSmall.into());
+    }
 
     // Burn v1 tokens
-    solana_program::program::invoke(
-        &spl_token::instruction::burn(
-            &spl_token::id(),
-            sender_info.key,
-            mint_v1_info.key,
-            signer_info.key,
-            &[signer_info.key],
-            amount,
-        )?,
-        &[
-            token_program.clone(),
-            sender_info.clone(),
-            mint_v1_info.clone(),
-            signer_info.clone(),
-        ],
+    burn_interface(
+        sender_info,
+        mint_v1_info,
+        signer_info,
+        migration_token_program,
+        amount,
     )?;
 
     // Cap at max supply.
-    if mint.supply.gt(&config.max_supply) {
+    if config
+        .emitted_supply(mint.supply)
+        .saturating_add(upgrade_amount)
+        .gt(&config.max_supply)
+    {
         return Err(OreError::MaxSupply.into());
     }
 
     // Mint to the beneficiary account
-    mint_to_signed(
+    mint_to_interface_signed(
         mint_info,
         beneficiary_info,
         treasury_info,
         token_program,
-        amount,
+        upgrade_amount,
         &[TREASURY],
     )?;
 
     Ok(())
-}
\ No newline at end of file
+}
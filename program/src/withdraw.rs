@@ -0,0 +1,59 @@
+use coal_api::prelude::*;
+use steel::*;
+
+/// Withdraw unstakes tokens from a boost.
+pub fn process_withdraw(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = Withdraw::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer_info, beneficiary_info, boost_info, stake_info, boost_tokens_info, mint_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    beneficiary_info
+        .is_writable()?
+        .as_token_account_interface()?
+        .assert(|t| t.mint == *mint_info.key)?;
+    let boost = boost_info
+        .is_writable()?
+        .as_account_mut::<Boost>(&coal_api::ID)?
+        .assert_mut(|b| b.mint == *mint_info.key)?;
+    let stake = stake_info
+        .is_writable()?
+        .as_account_mut::<Stake>(&coal_api::ID)?
+        .assert_mut_err(
+            |s| s.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert_mut_err(|s| s.boost == *boost_info.key, OreError::InvalidBoost.into())?;
+    boost_tokens_info
+        .is_writable()?
+        .as_associated_token_account_interface(boost_info.key, mint_info.key)?;
+    let mint = mint_info.as_mint_interface()?;
+    token_program.is_token_program()?;
+
+    // Update balances.
+    stake.balance = stake
+        .balance
+        .checked_sub(amount)
+        .ok_or(OreError::WithdrawTooLarge)?;
+    boost.total_stake = boost.total_stake.saturating_sub(amount);
+
+    // Transfer tokens from the boost to the beneficiary.
+    transfer_checked_signed(
+        boost_info,
+        boost_tokens_info,
+        mint_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        mint.decimals,
+        &[BOOST, mint_info.key.as_ref()],
+    )?;
+
+    Ok(())
+}
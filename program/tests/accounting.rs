@@ -0,0 +1,139 @@
+mod common;
+
+use coal_api::prelude::*;
+use common::*;
+use rand::Rng;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+const STEPS: usize = 64;
+
+/// Legacy tokens the payer holds to upgrade.
+const INITIAL_V1_BALANCE: u64 = 10u64.pow(TOKEN_DECIMALS_V1 as u32) * 1_000;
+
+#[tokio::test]
+async fn test_total_balance_matches_treasury_tokens() {
+    let mut context = setup(spl_token::ID).await;
+    let mut rng = rand::thread_rng();
+    let payer = context.payer.pubkey();
+
+    // Open a miner, whose proof mines through the ORE program.
+    let miner = Keypair::new();
+    let ix = coal_api::sdk::open(MINT_ADDRESS, miner.pubkey(), miner.pubkey(), payer);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+
+    // Open the migration, and give the payer legacy tokens to upgrade.
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.migration_mint = MINT_V1_ADDRESS;
+    set_state(&mut context, address, config);
+    context.set_account(
+        &MINT_V1_ADDRESS,
+        &mint_account(Pubkey::new_unique(), INITIAL_V1_BALANCE, TOKEN_DECIMALS_V1, spl_token::ID)
+            .into(),
+    );
+    let sender = token_account_address(payer, MINT_V1_ADDRESS, spl_token::ID);
+    context.set_account(
+        &sender,
+        &token_account(MINT_V1_ADDRESS, payer, INITIAL_V1_BALANCE, spl_token::ID).into(),
+    );
+
+    // Fund the busses before the first mine.
+    reset(&mut context).await;
+    let mut total_rewards = 0;
+    assert_totals(&mut context, &miner, total_rewards).await;
+
+    for _ in 0..STEPS {
+        match rng.gen_range(0..4) {
+            // Mine a solution to the miner's ORE challenge.
+            0 => {
+                let id = rng.gen_range(0..BUS_COUNT);
+                total_rewards += mine(&mut context, &miner, id).await;
+            }
+
+            // Warp past the epoch boundary and reset.
+            1 => reset(&mut context).await,
+
+            // Claim part of the treasury balance.
+            2 => {
+                let balance = treasury_balance(&mut context).await;
+                if balance == 0 {
+                    continue;
+                }
+                let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+                let amount = rng.gen_range(1..=balance);
+                let ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, amount);
+                process(&mut context, ix).await;
+            }
+
+            // Upgrade part of the legacy balance, which mints past the treasury.
+            _ => {
+                let balance = token_balance(&mut context, sender).await;
+                if balance == 0 {
+                    continue;
+                }
+                let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+                let amount = rng.gen_range(1..=balance);
+                let ix = coal_api::sdk::upgrade(
+                    MINT_ADDRESS,
+                    MINT_V1_ADDRESS,
+                    payer,
+                    beneficiary,
+                    sender,
+                    amount,
+                );
+                process(&mut context, ix).await;
+            }
+        }
+        assert_totals(&mut context, &miner, total_rewards).await;
+    }
+    assert!(total_rewards > 0);
+}
+
+/// Resets the epoch, then lowers the min difficulty the reset retunes so solutions are cheap.
+async fn reset(context: &mut ProgramTestContext) {
+    warp_to_next_epoch(context).await;
+    let ix = coal_api::sdk::reset(MINT_ADDRESS, context.payer.pubkey());
+    process(context, ix).await;
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(context, address).await;
+    config.min_difficulty = 1;
+    set_state(context, address, config);
+}
+
+/// Mines on the given bus and returns the reward of the mine event.
+async fn mine(context: &mut ProgramTestContext, miner: &Keypair, id: usize) -> u64 {
+    let proof = proof_pda(MINT_ADDRESS, miner.pubkey()).0;
+    let ore_proof = ore_api::state::proof_pda(proof).0;
+    let challenge = get_state::<ore_api::state::Proof>(context, ore_proof)
+        .await
+        .challenge;
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        miner.pubkey(),
+        miner.pubkey(),
+        bus_pda(MINT_ADDRESS, id as u8).0,
+        ore_api::consts::BUS_ADDRESSES[id],
+        solve(challenge, 1),
+        vec![],
+        None,
+    );
+    let data = process_with_return(context, &[ix], &[miner]).await;
+    bytemuck::pod_read_unaligned::<MineEvent>(&data).reward
+}
+
+async fn assert_totals(context: &mut ProgramTestContext, miner: &Keypair, total_rewards: u64) {
+    let config = get_state::<Config>(context, config_pda(MINT_ADDRESS).0).await;
+    let balance = treasury_balance(context).await;
+    assert_eq!(config.total_balance, balance);
+    assert_eq!(config.total_rewards, total_rewards);
+    let proof = get_state::<Proof>(context, proof_pda(MINT_ADDRESS, miner.pubkey()).0).await;
+    assert_eq!(proof.total_rewards, total_rewards);
+}
+
+async fn treasury_balance(context: &mut ProgramTestContext) -> u64 {
+    token_balance(context, TREASURY_TOKENS_ADDRESS).await
+}
@@ -0,0 +1,338 @@
+#![allow(dead_code)]
+
+mod stubs;
+
+use coal_api::prelude::*;
+use drillx::Solution;
+use solana_program::{program_pack::Pack, rent::Rent};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    signature::{keypair_from_seed, Keypair, Signer},
+    transaction::Transaction,
+};
+use steel::*;
+use stubs::*;
+
+/// Tokens minted to the payer's token account at setup.
+pub const INITIAL_BALANCE: u64 = ONE_ORE * 1_000;
+
+/// ORE held by the ORE treasury at setup, to pay ORE claims.
+pub const ORE_TREASURY_BALANCE: u64 = ONE_ORE * 1_000_000;
+
+/// Starts a test validator with the busses, config, treasury and mint of an initialized program.
+///
+/// The mint is owned by the given token program, and the payer has an open proof and a funded
+/// token account. The ORE and ORE boost accounts the program invokes with are also created, with
+/// funded ORE busses and treasury.
+pub async fn setup(token_program_id: Pubkey) -> ProgramTestContext {
+    let mut program_test = program_test();
+    add_ore_accounts(&mut program_test);
+
+    // Busses.
+    for id in 0..BUS_COUNT {
+        let bus = Bus {
+            id: id as u64,
+            rewards: 0,
+            theoretical_rewards: 0,
+        };
+        program_test.add_account(bus_pda(MINT_ADDRESS, id as u8).0, program_account(bus));
+    }
+
+    // Config.
+    let mut config = Config::zeroed();
+    config.mint = MINT_ADDRESS;
+    config.max_supply = ONE_ORE * 21_000_000;
+    config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+    config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+    config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+    config.schedule_epochs = 17_280;
+    config.decay_basis_points = 2000;
+    config.migration_ratio = 10_000;
+    program_test.add_account(config_pda(MINT_ADDRESS).0, program_account(config));
+
+    // Treasury and mint.
+    program_test.add_account(TREASURY_ADDRESS, program_account(Treasury {}));
+    program_test.add_account(
+        MINT_ADDRESS,
+        mint_account(TREASURY_ADDRESS, INITIAL_BALANCE, TOKEN_DECIMALS, token_program_id),
+    );
+    program_test.add_account(
+        token_account_address(TREASURY_ADDRESS, MINT_ADDRESS, token_program_id),
+        token_account(MINT_ADDRESS, TREASURY_ADDRESS, 0, token_program_id),
+    );
+
+    // The payer is not known until the context starts, so its accounts are written afterwards.
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+    let proof = Proof {
+        authority: payer,
+        balance: u64::MAX / 2,
+        last_stake_at: 0,
+        miner: payer,
+        total_hashes: 0,
+        total_rewards: 0,
+        bump: proof_pda(MINT_ADDRESS, payer).1 as u64,
+        last_mine_slot: 0,
+    };
+    set_state(&mut context, proof_pda(MINT_ADDRESS, payer).0, proof);
+    context.set_account(
+        &token_account_address(payer, MINT_ADDRESS, token_program_id),
+        &token_account(MINT_ADDRESS, payer, INITIAL_BALANCE, token_program_id).into(),
+    );
+    context
+}
+
+/// Adds the ORE config, busses, mint and treasury, the ORE boost directory, and this program's
+/// treasury ORE account.
+pub fn add_ore_accounts(program_test: &mut ProgramTest) {
+    use ore_api::consts::*;
+    let config = ore_api::state::Config {
+        base_reward_rate: INITIAL_BASE_REWARD_RATE,
+        last_reset_at: 0,
+        min_difficulty: INITIAL_MIN_DIFFICULTY as u64,
+        ..Zeroable::zeroed()
+    };
+    program_test.add_account(CONFIG_ADDRESS, owned_account(config, ore_api::ID));
+    for (id, address) in BUS_ADDRESSES.iter().enumerate() {
+        let bus = ore_api::state::Bus {
+            id: id as u64,
+            rewards: BUS_EPOCH_REWARDS,
+            ..Zeroable::zeroed()
+        };
+        program_test.add_account(*address, owned_account(bus, ore_api::ID));
+    }
+    program_test.add_account(
+        TREASURY_ADDRESS,
+        owned_account(ore_api::state::Treasury {}, ore_api::ID),
+    );
+    program_test.add_account(
+        MINT_ADDRESS,
+        mint_account(TREASURY_ADDRESS, ORE_TREASURY_BALANCE, TOKEN_DECIMALS, spl_token::ID),
+    );
+    program_test.add_account(
+        TREASURY_TOKENS_ADDRESS,
+        token_account(MINT_ADDRESS, TREASURY_ADDRESS, ORE_TREASURY_BALANCE, spl_token::ID),
+    );
+    program_test.add_account(
+        ore_boost_api::state::directory_pda().0,
+        owned_account(ore_boost_api::state::Directory::zeroed(), ore_boost_api::ID),
+    );
+    program_test.add_account(
+        token_account_address(coal_api::consts::TREASURY_ADDRESS, MINT_ADDRESS, spl_token::ID),
+        token_account(MINT_ADDRESS, coal_api::consts::TREASURY_ADDRESS, 0, spl_token::ID),
+    );
+}
+
+/// Creates a test validator with this program, and stand-ins for the ORE and ORE boost programs
+/// it invokes.
+pub fn program_test() -> ProgramTest {
+    // The stand-ins only have native processors, so they are never loaded from SBF builds.
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(false);
+    program_test.add_program("ore_stub", ore_api::ID, processor!(process_ore_instruction));
+    program_test.add_program(
+        "ore_boost_stub",
+        ore_boost_api::ID,
+        processor!(process_boost_instruction),
+    );
+    program_test.prefer_bpf(sbf());
+    program_test.add_program("ore", coal_api::ID, processor!(ore::process_instruction));
+    program_test
+}
+
+/// Returns true when the tests run against an SBF build of this program, as under `cargo test-sbf`.
+pub fn sbf() -> bool {
+    std::env::var("SBF_OUT_DIR").is_ok() || std::env::var("BPF_OUT_DIR").is_ok()
+}
+
+/// Returns the keypair of `INITIALIZER_ADDRESS` under the localnet feature.
+pub fn initializer() -> Keypair {
+    keypair_from_seed(&[7; 32]).unwrap()
+}
+
+/// Finds the first solution to the challenge with at least the given difficulty.
+pub fn solve(challenge: [u8; 32], difficulty: u32) -> Solution {
+    let mut memory = drillx::equix::SolverMemory::new();
+    for nonce in 0u64.. {
+        let nonce = nonce.to_le_bytes();
+        for hash in drillx::hashes_with_memory(&mut memory, &challenge, &nonce) {
+            if hash.difficulty().ge(&difficulty) {
+                return Solution::new(hash.d, nonce);
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Advances the clock by one epoch so the next reset is accepted.
+pub async fn warp_to_next_epoch(context: &mut ProgramTestContext) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = clock.unix_timestamp.saturating_add(EPOCH_DURATION);
+    context.set_sysvar(&clock);
+}
+
+pub fn token_account_address(owner: Pubkey, mint: Pubkey, token_program_id: Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(
+        &owner,
+        &mint,
+        &token_program_id,
+    )
+}
+
+pub async fn get_state<T: AccountDeserialize + Discriminator + Pod>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    *T::try_from_bytes(&account.data).unwrap()
+}
+
+pub fn set_state<T: Discriminator + Pod>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+    state: T,
+) {
+    context.set_account(&address, &program_account(state).into());
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Account::unpack(&account.data[..spl_token::state::Account::LEN])
+        .unwrap()
+        .amount
+}
+
+pub async fn mint_supply(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Mint::unpack(&account.data[..spl_token::state::Mint::LEN])
+        .unwrap()
+        .supply
+}
+
+pub async fn process(context: &mut ProgramTestContext, ix: Instruction) {
+    try_process(context, ix).await.unwrap();
+}
+
+pub async fn try_process(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+) -> Result<(), BanksClientError> {
+    try_process_signed(context, ix, &[]).await
+}
+
+/// Processes the instruction with signers besides the payer.
+pub async fn try_process_signed(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let tx = transaction(context, &[ix], signers).await;
+    context.banks_client.process_transaction(tx).await
+}
+
+/// Processes the instructions and returns the return data of the transaction.
+pub async fn process_with_return(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Vec<u8> {
+    let tx = transaction(context, ixs, signers).await;
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(tx)
+        .await
+        .unwrap();
+    result.result.unwrap();
+    result.metadata.unwrap().return_data.unwrap().data
+}
+
+async fn transaction(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Transaction {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let signers = [&[&context.payer], signers].concat();
+    Transaction::new_signed_with_payer(ixs, Some(&context.payer.pubkey()), &signers, blockhash)
+}
+
+pub fn program_account<T: Discriminator + Pod>(state: T) -> Account {
+    owned_account(state, coal_api::ID)
+}
+
+/// Serializes the state into an account owned by the given program.
+pub fn owned_account<T: Discriminator + Pod>(state: T, owner: Pubkey) -> Account {
+    let mut data = vec![0; 8 + std::mem::size_of::<T>()];
+    data[0] = T::discriminator();
+    data[8..].copy_from_slice(bytemuck::bytes_of(&state));
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Packs classic token state, which Token-2022 also accepts for accounts without extensions.
+pub fn packed_account<T: Pack>(state: T, token_program_id: Pubkey) -> Account {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: token_program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn mint_account(
+    authority: Pubkey,
+    supply: u64,
+    decimals: u8,
+    token_program_id: Pubkey,
+) -> Account {
+    packed_account(
+        spl_token::state::Mint {
+            mint_authority: Some(authority).into(),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        },
+        token_program_id,
+    )
+}
+
+pub fn token_account(mint: Pubkey, owner: Pubkey, amount: u64, token_program_id: Pubkey) -> Account {
+    packed_account(
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: None.into(),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        },
+        token_program_id,
+    )
+}
@@ -0,0 +1,273 @@
+use drillx::Solution;
+use ore_api::{
+    consts::{PROOF, TREASURY, TREASURY_ADDRESS, TREASURY_BUMP, TREASURY_TOKENS_ADDRESS},
+    event::MineEvent,
+    instruction::{Claim, Mine, OreInstruction},
+    state::{Bus, Config, Proof},
+};
+use ore_boost_api::{
+    consts::{BOOST_DENOMINATOR, RESERVATION},
+    instruction::BoostInstruction,
+    state::{directory_pda, Boost, Directory, Reservation},
+};
+use solana_program::{keccak::hashv, slot_hashes::SlotHash};
+use steel::*;
+
+/// Stands in for the ORE program in program tests.
+///
+/// Implements the instructions this program invokes, with the account checks and proof updates
+/// it relies on. Mining verifies the solution against the proof's challenge, rolls the challenge,
+/// and pays the base reward for its difficulty out of the bus, plus the multiplier of a reserved
+/// boost. Claims transfer ORE from the treasury. Reward penalties and the staker share of boost
+/// rewards are left out.
+pub fn process_ore_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (ix, data) = parse_instruction::<OreInstruction>(&ore_api::ID, program_id, data)?;
+    match ix {
+        OreInstruction::Claim => process_claim(accounts, data),
+        OreInstruction::Close => process_close(accounts),
+        OreInstruction::Mine => process_mine(accounts, data),
+        OreInstruction::Open => process_open(accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+/// Stands in for the ORE boost program in program tests.
+///
+/// Registers reservations, and rotates them to the first boost of the directory, or to none if
+/// the directory is empty.
+pub fn process_boost_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let (ix, _) = parse_instruction::<BoostInstruction>(&ore_boost_api::ID, program_id, data)?;
+    match ix {
+        BoostInstruction::Register => process_register(accounts),
+        BoostInstruction::Rotate => process_rotate(accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn process_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = Claim::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    let [signer_info, beneficiary_info, proof_info, treasury_info, treasury_tokens_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    beneficiary_info.is_writable()?.as_token_account()?;
+    treasury_info.has_address(&TREASURY_ADDRESS)?;
+    treasury_tokens_info
+        .is_writable()?
+        .has_address(&TREASURY_TOKENS_ADDRESS)?;
+    token_program.is_program(&spl_token::ID)?;
+    let proof = proof_info
+        .as_account_mut::<Proof>(&ore_api::ID)?
+        .assert_mut_err(
+            |p| p.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?;
+    proof.balance = proof
+        .balance
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    transfer_signed_with_bump(
+        treasury_info,
+        treasury_tokens_info,
+        beneficiary_info,
+        token_program,
+        amount,
+        &[TREASURY],
+        TREASURY_BUMP,
+    )
+}
+
+fn process_close(accounts: &[AccountInfo]) -> ProgramResult {
+    let [signer_info, proof_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    proof_info
+        .is_writable()?
+        .as_account::<Proof>(&ore_api::ID)?
+        .assert_err(
+            |p| p.authority == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?
+        .assert(|p| p.balance == 0)?;
+    system_program.is_program(&system_program::ID)?;
+    proof_info.close(signer_info)
+}
+
+fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = Mine::try_from_bytes(data)?;
+    let clock = Clock::get()?;
+    let (required_accounts, boost_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer_info, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar] =
+        required_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let bus = bus_info.is_writable()?.as_account_mut::<Bus>(&ore_api::ID)?;
+    let config = config_info.as_account::<Config>(&ore_api::ID)?;
+    let proof = proof_info
+        .is_writable()?
+        .as_account_mut::<Proof>(&ore_api::ID)?
+        .assert_mut_err(
+            |p| p.miner == *signer_info.key,
+            ProgramError::MissingRequiredSignature,
+        )?;
+    instructions_sysvar.is_sysvar(&sysvar::instructions::ID)?;
+    slot_hashes_sysvar.is_sysvar(&sysvar::slot_hashes::ID)?;
+
+    // Verify the solution and roll the challenge.
+    let solution = Solution::new(args.digest, args.nonce);
+    if !solution.is_valid(&proof.challenge) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let hash = solution.to_hash();
+    proof.challenge = hashv(&[
+        hash.h.as_slice(),
+        &slot_hashes_sysvar.data.borrow()[0..std::mem::size_of::<SlotHash>()],
+    ])
+    .0;
+
+    // Pay the base reward for the difficulty, plus the multiplier of the reserved boost, capped by
+    // the bus.
+    let difficulty = hash.difficulty();
+    let normalized_difficulty = (difficulty as u64)
+        .checked_sub(config.min_difficulty)
+        .ok_or(ProgramError::InvalidArgument)?;
+    let base_reward = config
+        .base_reward_rate
+        .saturating_mul(2u64.saturating_pow(normalized_difficulty as u32));
+    let mut boost_reward = 0;
+    if let [boost_info, _boost_proof_info, reservation_info] = boost_accounts {
+        let boost = boost_info.as_account::<Boost>(&ore_boost_api::ID)?;
+        let reservation = reservation_info
+            .as_account::<Reservation>(&ore_boost_api::ID)?
+            .assert(|r| r.authority == *proof_info.key)?;
+        if reservation.boost == *boost_info.key && boost.expires_at > clock.unix_timestamp {
+            boost_reward = ((base_reward as u128) * (boost.multiplier as u128)
+                / BOOST_DENOMINATOR as u128) as u64;
+        }
+    }
+    let reward = base_reward.saturating_add(boost_reward).min(bus.rewards);
+    bus.rewards -= reward;
+    proof.balance = proof.balance.saturating_add(reward);
+    proof.last_hash = hash.h;
+    proof.last_hash_at = clock.unix_timestamp;
+    proof.total_hashes = proof.total_hashes.saturating_add(1);
+    proof.total_rewards = proof.total_rewards.saturating_add(reward);
+
+    MineEvent {
+        balance: proof.balance,
+        difficulty: difficulty as u64,
+        last_hash_at: proof.last_hash_at,
+        timing: 0,
+        net_reward: reward,
+        net_base_reward: reward.min(base_reward),
+        net_miner_boost_reward: reward.saturating_sub(base_reward),
+        net_staker_boost_reward: 0,
+    }
+    .log_return();
+    Ok(())
+}
+
+fn process_open(accounts: &[AccountInfo]) -> ProgramResult {
+    let clock = Clock::get()?;
+    let [signer_info, miner_info, payer_info, proof_info, system_program, slot_hashes_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    payer_info.is_signer()?;
+    proof_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[PROOF, signer_info.key.as_ref()], &ore_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+    slot_hashes_info.is_sysvar(&sysvar::slot_hashes::ID)?;
+
+    create_account::<Proof>(
+        proof_info,
+        system_program,
+        payer_info,
+        &ore_api::ID,
+        &[PROOF, signer_info.key.as_ref()],
+    )?;
+    let proof = proof_info.as_account_mut::<Proof>(&ore_api::ID)?;
+    proof.authority = *signer_info.key;
+    proof.balance = 0;
+    proof.challenge = hashv(&[
+        signer_info.key.as_ref(),
+        &slot_hashes_info.data.borrow()[0..std::mem::size_of::<SlotHash>()],
+    ])
+    .0;
+    proof.last_hash = [0; 32];
+    proof.last_hash_at = clock.unix_timestamp;
+    proof.miner = *miner_info.key;
+    proof.total_hashes = 0;
+    proof.total_rewards = 0;
+    Ok(())
+}
+
+fn process_register(accounts: &[AccountInfo]) -> ProgramResult {
+    let [signer_info, payer_info, proof_info, reservation_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    payer_info.is_signer()?;
+    proof_info
+        .as_account::<Proof>(&ore_api::ID)?
+        .assert(|p| p.authority == *signer_info.key)?;
+    reservation_info
+        .is_empty()?
+        .is_writable()?
+        .has_seeds(&[RESERVATION, proof_info.key.as_ref()], &ore_boost_api::ID)?;
+    system_program.is_program(&system_program::ID)?;
+
+    create_account::<Reservation>(
+        reservation_info,
+        system_program,
+        payer_info,
+        &ore_boost_api::ID,
+        &[RESERVATION, proof_info.key.as_ref()],
+    )?;
+    let reservation = reservation_info.as_account_mut::<Reservation>(&ore_boost_api::ID)?;
+    reservation.authority = *proof_info.key;
+    Ok(())
+}
+
+fn process_rotate(accounts: &[AccountInfo]) -> ProgramResult {
+    let [signer_info, directory_info, proof_info, reservation_info, _treasury_tokens_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    signer_info.is_signer()?;
+    let directory = directory_info
+        .has_address(&directory_pda().0)?
+        .as_account::<Directory>(&ore_boost_api::ID)?;
+    proof_info
+        .as_account::<Proof>(&ore_api::ID)?
+        .assert(|p| p.authority == *signer_info.key)?;
+    let reservation = reservation_info
+        .is_writable()?
+        .as_account_mut::<Reservation>(&ore_boost_api::ID)?
+        .assert_mut(|r| r.authority == *proof_info.key)?;
+    reservation.boost = if directory.len > 0 {
+        directory.boosts[0]
+    } else {
+        Pubkey::default()
+    };
+    Ok(())
+}
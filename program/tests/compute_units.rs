@@ -0,0 +1,283 @@
+mod common;
+
+use coal_api::prelude::*;
+use common::*;
+use solana_program::instruction::Instruction;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// How far an instruction may exceed its recorded compute units before the bench fails, in basis
+/// points. It must stay below the SDK's `COMPUTE_UNIT_HEADROOM_BPS`.
+const REGRESSION_THRESHOLD_BPS: u64 = 500;
+
+/// The compute units an instruction consumed, and the constant that records them.
+struct Case {
+    constant: &'static str,
+    recorded: u32,
+    units: u64,
+}
+
+/// Measures the compute units of each instruction that runs in program-test.
+///
+/// Native builds only meter the CPIs into SBF programs, so the bench is ignored by `cargo test`
+/// and fails when run outside of `cargo test-sbf --features localnet`. With `BENCH_UPDATE` set, an
+/// SBF run rewrites the recorded constants in api/src/consts.rs, which the SDK uses to size compute
+/// unit limits.
+///
+/// Mine is measured with 0 to `MAX_COAL_BOOSTS` COAL boosts, and records the cost of the most
+/// expensive boost. The ORE and ORE boost programs that open and mine invoke are unmetered native
+/// stand-ins, so their numbers only cover this program, including the cost of the CPIs.
+#[tokio::test]
+#[ignore = "needs an SBF build: cargo test-sbf --features localnet --test compute_units -- --include-ignored"]
+async fn test_compute_units() {
+    assert!(
+        sbf(),
+        "The compute unit bench needs an SBF build of the program, under cargo test-sbf"
+    );
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let mut cases = vec![];
+
+    warp_to_next_epoch(&mut context).await;
+    let ix = coal_api::sdk::reset(MINT_ADDRESS, payer);
+    cases.push(measure(&mut context, ix.clone(), "RESET_COMPUTE_UNITS", RESET_COMPUTE_UNITS).await);
+    process(&mut context, ix).await;
+
+    // Open a miner.
+    let miner = Keypair::new();
+    let signer = miner.pubkey();
+    let ix = coal_api::sdk::open(MINT_ADDRESS, signer, signer, payer);
+    cases.push(
+        measure_signed(&mut context, ix.clone(), &[&miner], "OPEN_COMPUTE_UNITS", OPEN_COMPUTE_UNITS)
+            .await,
+    );
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+
+    // Mine with each number of settled COAL boosts. The reset retunes the min difficulty, so it is
+    // lowered for a cheap solution.
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.min_difficulty = 1;
+    set_state(&mut context, address, config);
+    let ore_proof = ore_api::state::proof_pda(proof_pda(MINT_ADDRESS, signer).0).0;
+    let challenge = get_state::<ore_api::state::Proof>(&mut context, ore_proof)
+        .await
+        .challenge;
+    let solution = solve(challenge, 1);
+    let mut boost_mints = vec![];
+    let mut units = vec![];
+    for boosts in 0..=MAX_COAL_BOOSTS {
+        if boosts > 0 {
+            boost_mints.push(open_boost(&mut context, signer));
+        }
+        let ix = coal_api::sdk::mine(
+            MINT_ADDRESS,
+            signer,
+            signer,
+            bus_pda(MINT_ADDRESS, 0).0,
+            ore_api::consts::BUS_ADDRESSES[0],
+            solution,
+            coal_api::sdk::coal_boost_accounts(signer, &boost_mints),
+            None,
+        );
+        let case = measure_signed(
+            &mut context,
+            ix,
+            &[&miner],
+            "MINE_COMPUTE_UNITS",
+            MINE_COMPUTE_UNITS,
+        )
+        .await;
+        units.push(case.units);
+    }
+    cases.push(Case {
+        constant: "MINE_COMPUTE_UNITS",
+        recorded: MINE_COMPUTE_UNITS,
+        units: units[0],
+    });
+    cases.push(Case {
+        constant: "COAL_BOOST_COMPUTE_UNITS",
+        recorded: COAL_BOOST_COMPUTE_UNITS,
+        units: (1..units.len())
+            .map(|boosts| units[boosts].saturating_sub(units[0]).div_ceil(boosts as u64))
+            .max()
+            .unwrap(),
+    });
+
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, ONE_ORE);
+    cases.push(measure(&mut context, ix, "CLAIM_COMPUTE_UNITS", CLAIM_COMPUTE_UNITS).await);
+
+    let ix = coal_api::sdk::update(MINT_ADDRESS, payer, payer);
+    cases.push(measure(&mut context, ix, "UPDATE_COMPUTE_UNITS", UPDATE_COMPUTE_UNITS).await);
+
+    #[cfg(feature = "boosts")]
+    {
+        let boost = boost_pda(MINT_ADDRESS);
+        set_state(
+            &mut context,
+            boost.0,
+            Boost {
+                mint: MINT_ADDRESS,
+                multiplier: 10_000,
+                expires_at: i64::MAX,
+                total_stake: 0,
+                bump: boost.1 as u64,
+            },
+        );
+        context.set_account(
+            &token_account_address(boost.0, MINT_ADDRESS, spl_token::ID),
+            &token_account(MINT_ADDRESS, boost.0, 0, spl_token::ID).into(),
+        );
+        let ix = coal_api::sdk::open_stake(MINT_ADDRESS, payer);
+        cases.push(
+            measure(&mut context, ix.clone(), "OPEN_STAKE_COMPUTE_UNITS", OPEN_STAKE_COMPUTE_UNITS)
+                .await,
+        );
+        process(&mut context, ix).await;
+        let ix = coal_api::sdk::deposit(MINT_ADDRESS, payer, beneficiary, ONE_ORE, &spl_token::ID);
+        cases.push(
+            measure(&mut context, ix.clone(), "DEPOSIT_COMPUTE_UNITS", DEPOSIT_COMPUTE_UNITS).await,
+        );
+        process(&mut context, ix).await;
+        let ix = coal_api::sdk::withdraw(MINT_ADDRESS, payer, beneficiary, ONE_ORE, &spl_token::ID);
+        cases.push(measure(&mut context, ix, "WITHDRAW_COMPUTE_UNITS", WITHDRAW_COMPUTE_UNITS).await);
+    }
+
+    #[cfg(feature = "pools")]
+    {
+        // A pool whose proof holds the member's rewards.
+        let pool = pool_pda(MINT_ADDRESS, payer);
+        set_state(
+            &mut context,
+            pool.0,
+            Pool {
+                authority: payer,
+                proof: proof_pda(MINT_ADDRESS, pool.0).0,
+                total_members: 0,
+                total_hashes: 0,
+                total_difficulty: 0,
+                bump: pool.1 as u64,
+            },
+        );
+        let mut pool_proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+        pool_proof.authority = pool.0;
+        set_state(&mut context, proof_pda(MINT_ADDRESS, pool.0).0, pool_proof);
+        let ix = coal_api::sdk::join_pool(pool.0, payer);
+        cases.push(
+            measure(&mut context, ix.clone(), "JOIN_POOL_COMPUTE_UNITS", JOIN_POOL_COMPUTE_UNITS)
+                .await,
+        );
+        process(&mut context, ix).await;
+        let member = pool_member_pda(pool.0, payer).0;
+        let mut member_state = get_state::<PoolMember>(&mut context, member).await;
+        member_state.balance = ONE_ORE;
+        set_state(&mut context, member, member_state);
+        let ix = coal_api::sdk::claim_pool(MINT_ADDRESS, payer, pool.0, beneficiary, ONE_ORE);
+        cases.push(
+            measure(&mut context, ix, "CLAIM_POOL_COMPUTE_UNITS", CLAIM_POOL_COMPUTE_UNITS).await,
+        );
+    }
+
+    // Either record the numbers, or fail on regressions past the threshold.
+    if std::env::var("BENCH_UPDATE").is_ok() {
+        update_consts(&cases);
+        return;
+    }
+    let regressions: Vec<String> = cases
+        .iter()
+        .filter(|case| {
+            case.units > case.recorded as u64 * (10_000 + REGRESSION_THRESHOLD_BPS) / 10_000
+        })
+        .map(|case| format!("{} {} of {}", case.constant, case.units, case.recorded))
+        .collect();
+    assert!(regressions.is_empty(), "Compute unit regressions: {:?}", regressions);
+}
+
+/// Creates a boost for a new mint, settles a stake of the authority in it, and returns the mint.
+fn open_boost(context: &mut ProgramTestContext, authority: Pubkey) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let boost = boost_pda(mint);
+    set_state(
+        context,
+        boost.0,
+        Boost {
+            mint,
+            multiplier: 10_000,
+            expires_at: i64::MAX,
+            total_stake: ONE_ORE,
+            bump: boost.1 as u64,
+        },
+    );
+    let stake = stake_pda(boost.0, authority);
+    set_state(
+        context,
+        stake.0,
+        Stake {
+            authority,
+            boost: boost.0,
+            balance: ONE_ORE,
+            last_deposit_at: 0,
+            bump: stake.1 as u64,
+        },
+    );
+    mint
+}
+
+/// Simulates the instruction and returns the compute units it consumed.
+async fn measure(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    constant: &'static str,
+    recorded: u32,
+) -> Case {
+    measure_signed(context, ix, &[], constant, recorded).await
+}
+
+/// Simulates the instruction with signers besides the payer.
+async fn measure_signed(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    signers: &[&Keypair],
+    constant: &'static str,
+    recorded: u32,
+) -> Case {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let signers = [&[&context.payer], signers].concat();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &signers,
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    Case {
+        constant,
+        recorded,
+        units: simulation.simulation_details.unwrap().units_consumed,
+    }
+}
+
+/// Rewrites the recorded constants with the measured compute units.
+fn update_consts(cases: &[Case]) {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../api/src/consts.rs");
+    let consts = std::fs::read_to_string(path).unwrap();
+    let lines: Vec<String> = consts
+        .lines()
+        .map(|line| {
+            match cases
+                .iter()
+                .find(|case| line.starts_with(&format!("pub const {}: u32 = ", case.constant)))
+            {
+                Some(case) => format!("pub const {}: u32 = {};", case.constant, case.units),
+                None => line.to_string(),
+            }
+        })
+        .collect();
+    std::fs::write(path, lines.join("\n") + "\n").unwrap();
+}
@@ -0,0 +1,1172 @@
+mod common;
+
+use coal_api::prelude::*;
+use common::*;
+use solana_program::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+#[tokio::test]
+async fn test_initialize() {
+    let mut program_test = program_test();
+    let initializer = initializer();
+    program_test.add_account(
+        initializer.pubkey(),
+        Account {
+            lamports: 10 * LAMPORTS_PER_SOL,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        ORE_MINT_ADDRESS,
+        mint_account(Pubkey::new_unique(), 0, TOKEN_DECIMALS, spl_token::ID),
+    );
+    let mut context = program_test.start_with_context().await;
+
+    // Token-2022 mints carry their own metadata, so no Metaplex program is needed.
+    let ix = coal_api::sdk::initialize_with_program_id(
+        initializer.pubkey(),
+        MINT_NOISE,
+        &spl_token_2022::ID,
+    );
+    try_process_signed(&mut context, ix, &[&initializer]).await.unwrap();
+    for id in 0..BUS_COUNT as u8 {
+        let bus = get_state::<Bus>(&mut context, bus_pda(MINT_ADDRESS, id).0).await;
+        assert_eq!(bus.id, id as u64);
+    }
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.mint, MINT_ADDRESS);
+    assert_eq!(config.max_supply, ONE_ORE * 21_000_000);
+    assert_eq!(config.schedule_epochs, 17_280);
+    assert_eq!(config.migration_mint, MINT_V1_ADDRESS);
+    assert_eq!(config.migration_ratio, 10_000);
+    assert_eq!(config.min_difficulty, INITIAL_MIN_DIFFICULTY as u64);
+    let mint = context.banks_client.get_account(MINT_ADDRESS).await.unwrap().unwrap();
+    assert_eq!(mint.owner, spl_token_2022::ID);
+    let treasury_tokens = token_account_address(TREASURY_ADDRESS, MINT_ADDRESS, spl_token_2022::ID);
+    assert_eq!(token_balance(&mut context, treasury_tokens).await, 0);
+}
+
+#[tokio::test]
+async fn test_migrate_config() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let address = config_pda(MINT_ADDRESS).0;
+    let config = get_state::<Config>(&mut context, address).await;
+
+    // A config created before the supply accounting fields, cut off after the ingredients.
+    let legacy_len = 8 + std::mem::offset_of!(Config, total_redeemed);
+    let mut account = program_account(config);
+    account.data.truncate(legacy_len);
+    account.lamports = Rent::default().minimum_balance(legacy_len);
+    context.set_account(&address, &account.clone().into());
+    warp_to_next_epoch(&mut context).await;
+    let ix = coal_api::sdk::reset(MINT_ADDRESS, payer);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::NeedsMigration as u32)
+        )
+    );
+
+    // Migrate grows it to the current layout, keeping the fields it had.
+    process(&mut context, coal_api::sdk::migrate(payer, address)).await;
+    let migrated = context.banks_client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(migrated.data.len(), 8 + std::mem::size_of::<Config>());
+    assert_eq!(migrated.data[..legacy_len], account.data[..]);
+    assert_eq!(migrated.lamports, Rent::default().minimum_balance(migrated.data.len()));
+    let migrated = get_state::<Config>(&mut context, address).await;
+    assert_eq!(
+        migrated,
+        Config {
+            total_redeemed: 0,
+            total_ore_redeemed: 0,
+            total_burned: 0,
+            recycle_burns: 0,
+            migration_ratio: 10_000,
+            migration_deadline: 0,
+            crank_reward: 0,
+            mine_policy: 0,
+            mine_cpi_allowlist: [Pubkey::default(); MINE_CPI_ALLOWLIST_SIZE],
+            ore_balance: 0,
+            ..config
+        }
+    );
+
+    // Migrating a current account leaves it as it is.
+    process(&mut context, coal_api::sdk::migrate(payer, address)).await;
+    assert_eq!(get_state::<Config>(&mut context, address).await, migrated);
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+
+    // Only configs at their own address are migrated.
+    let other = Pubkey::new_unique();
+    context.set_account(&other, &account.into());
+    let ix = coal_api::sdk::migrate(payer, other);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+}
+
+#[tokio::test]
+async fn test_migrate_proof() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let address = proof_pda(MINT_ADDRESS, payer).0;
+    let proof = get_state::<Proof>(&mut context, address).await;
+
+    // A proof created before it recorded its last mined slot.
+    let legacy_len = 8 + std::mem::offset_of!(Proof, last_mine_slot);
+    let mut account = program_account(proof);
+    account.data.truncate(legacy_len);
+    account.lamports = Rent::default().minimum_balance(legacy_len);
+    context.set_account(&address, &account.clone().into());
+    let ix = coal_api::sdk::update(MINT_ADDRESS, payer, payer);
+    let err = try_process(&mut context, ix.clone()).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::NeedsMigration as u32)
+        )
+    );
+
+    // Migrate grows it to the current layout, keeping the fields it had.
+    process(&mut context, coal_api::sdk::migrate(payer, address)).await;
+    let migrated = context.banks_client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(migrated.data.len(), 8 + std::mem::size_of::<Proof>());
+    assert_eq!(migrated.data[..legacy_len], account.data[..]);
+    assert_eq!(migrated.lamports, Rent::default().minimum_balance(migrated.data.len()));
+    let migrated = get_state::<Proof>(&mut context, address).await;
+    assert_eq!(migrated, Proof { last_mine_slot: 0, ..proof });
+    process(&mut context, ix).await;
+}
+
+#[tokio::test]
+async fn test_reset() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    // Reset tops up every bus and funds the treasury.
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(config.last_reset_at, clock.unix_timestamp);
+    for id in 0..BUS_COUNT as u8 {
+        let bus = get_state::<Bus>(&mut context, bus_pda(MINT_ADDRESS, id).0).await;
+        assert_eq!(bus.id, id as u64);
+        assert_eq!(bus.rewards, config.get_epoch_rewards());
+        assert_eq!(bus.theoretical_rewards, 0);
+    }
+    let balance = token_balance(&mut context, TREASURY_TOKENS_ADDRESS).await;
+    assert_eq!(balance, config.total_balance);
+    assert_eq!(
+        mint_supply(&mut context, MINT_ADDRESS).await,
+        INITIAL_BALANCE + balance
+    );
+
+    // A second reset within the same epoch is a no-op.
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(config.total_balance, balance);
+}
+
+#[tokio::test]
+async fn test_reset_crank_reward() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.crank_reward = ONE_ORE / 100;
+    set_state(&mut context, address, config);
+    warp_to_next_epoch(&mut context).await;
+
+    // The first reset of the epoch pays the crank reward on top of the treasury funding.
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::reset_with_args(
+        MINT_ADDRESS,
+        payer,
+        &spl_token::ID,
+        Reset { strict: 0 },
+        Some(beneficiary),
+    );
+    process(&mut context, ix).await;
+    let config = get_state::<Config>(&mut context, address).await;
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + ONE_ORE / 100
+    );
+    assert_eq!(
+        mint_supply(&mut context, MINT_ADDRESS).await,
+        INITIAL_BALANCE + config.total_balance + ONE_ORE / 100
+    );
+
+    // Later resets in the same epoch are no-ops and pay nothing.
+    let ix = coal_api::sdk::reset_with_args(
+        MINT_ADDRESS,
+        payer,
+        &spl_token::ID,
+        Reset { strict: 0 },
+        Some(beneficiary),
+    );
+    process(&mut context, ix).await;
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + ONE_ORE / 100
+    );
+}
+
+#[tokio::test]
+async fn test_reset_strict() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    let ix = coal_api::sdk::reset_strict(MINT_ADDRESS, payer, &spl_token::ID);
+    process(&mut context, ix).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.current_epoch, 1);
+    assert_eq!(next_reset_at(&config), config.last_reset_at + EPOCH_DURATION);
+
+    // A strict reset within the same epoch fails instead of no-oping.
+    let ix = coal_api::sdk::reset_strict(MINT_ADDRESS, payer, &spl_token::ID);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::ResetTooEarly as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_claim() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+
+    // Claim transfers from the treasury and debits the proof.
+    let amount = ONE_ORE;
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let treasury_balance = token_balance(&mut context, TREASURY_TOKENS_ADDRESS).await;
+    let ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, amount);
+    process(&mut context, ix).await;
+    let proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(proof.balance, u64::MAX / 2 - amount);
+    assert_eq!(config.total_balance, treasury_balance - amount);
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + amount
+    );
+    assert_eq!(
+        token_balance(&mut context, TREASURY_TOKENS_ADDRESS).await,
+        treasury_balance - amount
+    );
+}
+
+#[tokio::test]
+async fn test_claim_too_large() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let address = proof_pda(MINT_ADDRESS, payer).0;
+    let mut proof = get_state::<Proof>(&mut context, address).await;
+    proof.balance = 10;
+    set_state(&mut context, address, proof);
+
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, 11);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::ClaimTooLarge as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_claim_other_mint() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let mint = second_mint(&mut context).await;
+
+    // A balance earned on one mint cannot be claimed in the tokens of another.
+    let beneficiary = token_account_address(payer, mint, spl_token::ID);
+    let mut ix = coal_api::sdk::claim(mint, payer, beneficiary, ONE_ORE);
+    ix.accounts[2].pubkey = proof_pda(MINT_ADDRESS, payer).0;
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+    assert_eq!(token_balance(&mut context, beneficiary).await, 0);
+}
+
+#[tokio::test]
+async fn test_claim_other_treasury_ore_tokens() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let address = proof_pda(MINT_ADDRESS, payer).0;
+    let mut proof = get_state::<Proof>(&mut context, address).await;
+    proof.balance = ONE_ORE;
+    set_state(&mut context, address, proof);
+
+    // The ORE swept from the ORE proof must go to the treasury's ORE account.
+    let ore_tokens = token_account_address(payer, ORE_MINT_ADDRESS, spl_token::ID);
+    context.set_account(
+        &ore_tokens,
+        &token_account(ORE_MINT_ADDRESS, payer, 0, spl_token::ID).into(),
+    );
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let mut ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, ONE_ORE);
+    ix.accounts[8].pubkey = ore_tokens;
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn test_redeem() {
+    use steel::Zeroable;
+
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+
+    // Claiming the full balance sweeps the ORE proof into the treasury, backing the mint.
+    let proof_address = proof_pda(MINT_ADDRESS, payer).0;
+    let mut proof = get_state::<Proof>(&mut context, proof_address).await;
+    proof.balance = ONE_ORE;
+    set_state(&mut context, proof_address, proof);
+    let ore_proof_address = ore_api::state::proof_pda(proof_address).0;
+    let ore_reward = ONE_ORE * 4;
+    let mut ore_proof = ore_api::state::Proof::zeroed();
+    ore_proof.authority = proof_address;
+    ore_proof.balance = ore_reward;
+    ore_proof.miner = MINT_ADDRESS;
+    context.set_account(&ore_proof_address, &owned_account(ore_proof, ore_api::ID).into());
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::claim(MINT_ADDRESS, payer, beneficiary, ONE_ORE);
+    process(&mut context, ix).await;
+    let treasury_ore_tokens =
+        token_account_address(TREASURY_ADDRESS, ORE_MINT_ADDRESS, spl_token::ID);
+    assert_eq!(token_balance(&mut context, treasury_ore_tokens).await, ore_reward);
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.ore_balance, ore_reward);
+
+    // Redeem pays a pro-rata share of the mint's backing for the burned COAL.
+    let ore_beneficiary = token_account_address(payer, ORE_MINT_ADDRESS, spl_token::ID);
+    context.set_account(
+        &ore_beneficiary,
+        &token_account(ORE_MINT_ADDRESS, payer, 0, spl_token::ID).into(),
+    );
+    let supply = mint_supply(&mut context, MINT_ADDRESS).await;
+    let amount = INITIAL_BALANCE;
+    let ore_amount = (ore_reward as u128 * amount as u128 / supply as u128) as u64;
+    assert!(ore_amount > 0);
+    let ix = coal_api::sdk::redeem(MINT_ADDRESS, payer, ore_beneficiary, beneficiary, amount);
+    process(&mut context, ix).await;
+    assert_eq!(token_balance(&mut context, ore_beneficiary).await, ore_amount);
+    assert_eq!(
+        token_balance(&mut context, treasury_ore_tokens).await,
+        ore_reward - ore_amount
+    );
+    assert_eq!(mint_supply(&mut context, MINT_ADDRESS).await, supply - amount);
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(config.ore_balance, ore_reward - ore_amount);
+    assert_eq!(config.total_redeemed, amount);
+    assert_eq!(config.total_ore_redeemed, ore_amount);
+
+    // Another mint has no claim on that ORE.
+    let mint = second_mint(&mut context).await;
+    let address = config_pda(mint).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.ore_balance = 0;
+    set_state(&mut context, address, config);
+    let sender = token_account_address(payer, mint, spl_token::ID);
+    context.set_account(
+        &sender,
+        &token_account(mint, payer, INITIAL_BALANCE, spl_token::ID).into(),
+    );
+    let ix = coal_api::sdk::redeem(mint, payer, ore_beneficiary, sender, INITIAL_BALANCE);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::RedeemTooSmall as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_mine_invalid_accounts() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        payer,
+        payer,
+        bus_pda(MINT_ADDRESS, 0).0,
+        ore_api::consts::BUS_ADDRESSES[0],
+        drillx::Solution::new([0; 16], [0; 8]),
+        vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        None,
+    );
+
+    // Accounts past the counts in the args are rejected.
+    let mut extra = ix.clone();
+    extra.accounts.push(extra.accounts[15].clone());
+    let err = try_process(&mut context, extra).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::InvalidMineAccounts as u32)
+        )
+    );
+
+    // Missing accounts are rejected instead of panicking.
+    let mut missing = ix.clone();
+    missing.accounts.truncate(15);
+    let err = try_process(&mut context, missing).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[tokio::test]
+async fn test_mine_mismatched_accounts() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        payer,
+        payer,
+        bus_pda(MINT_ADDRESS, 0).0,
+        ore_api::consts::BUS_ADDRESSES[0],
+        drillx::Solution::new([0; 16], [0; 8]),
+        vec![],
+        None,
+    );
+
+    // A mint other than the config's.
+    let mut mint = ix.clone();
+    mint.accounts[1].pubkey = Pubkey::new_unique();
+    assert_mine_err(&mut context, mint, OreError::InvalidMint).await;
+
+    // A bus account that is not seeded by the config's mint.
+    let address = Pubkey::new_unique();
+    let bus = get_state::<Bus>(&mut context, bus_pda(MINT_ADDRESS, 0).0).await;
+    set_state(&mut context, address, bus);
+    let mut bus = ix.clone();
+    bus.accounts[2].pubkey = address;
+    assert_mine_err(&mut context, bus, OreError::InvalidBus).await;
+
+    // A proof account that is not seeded by the config's mint.
+    let address = Pubkey::new_unique();
+    let proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+    set_state(&mut context, address, proof);
+    let mut proof = ix.clone();
+    proof.accounts[4].pubkey = address;
+    assert_mine_err(&mut context, proof, OreError::InvalidProof).await;
+
+    // ORE accounts that are not derived from the proof.
+    for index in [5, 6, 7, 8, 9, 10] {
+        let mut ore = ix.clone();
+        ore.accounts[index].pubkey = Pubkey::new_unique();
+        assert_mine_err(&mut context, ore, OreError::InvalidOreAccounts).await;
+    }
+}
+
+#[tokio::test]
+async fn test_open_mine_close() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let miner = Keypair::new();
+    let signer = miner.pubkey();
+
+    // Open creates the proof, and the ORE proof and boost reservation it mines through.
+    let ix = coal_api::sdk::open(MINT_ADDRESS, signer, signer, payer);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+    let proof_address = proof_pda(MINT_ADDRESS, signer).0;
+    let proof = get_state::<Proof>(&mut context, proof_address).await;
+    assert_eq!(proof.authority, signer);
+    assert_eq!(proof.miner, signer);
+    let ore_proof_address = ore_api::state::proof_pda(proof_address).0;
+    let ore_proof = get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    assert_eq!(ore_proof.authority, proof_address);
+    assert_eq!(ore_proof.miner, MINT_ADDRESS);
+    let reservation_address = ore_boost_api::state::reservation_pda(ore_proof_address).0;
+    let reservation =
+        get_state::<ore_boost_api::state::Reservation>(&mut context, reservation_address).await;
+    assert_eq!(reservation.authority, ore_proof_address);
+
+    // Mine a solution to the ORE proof's challenge at difficulty 1. The reset retunes the min
+    // difficulty, so it is lowered afterwards.
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.min_difficulty = 1;
+    set_state(&mut context, address, config);
+    let bus_address = bus_pda(MINT_ADDRESS, 0).0;
+    let bus = get_state::<Bus>(&mut context, bus_address).await;
+    let solution = solve(ore_proof.challenge, 1);
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        signer,
+        signer,
+        bus_address,
+        ore_api::consts::BUS_ADDRESSES[0],
+        solution,
+        vec![],
+        None,
+    );
+    let data = process_with_return(&mut context, &[ix], &[&miner]).await;
+    let event = bytemuck::pod_read_unaligned::<MineEvent>(&data);
+    assert_eq!(event.difficulty, solution.to_hash().difficulty() as u64);
+    assert!(event.reward > 0);
+    assert_eq!(event.balance, event.reward);
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let proof = get_state::<Proof>(&mut context, proof_address).await;
+    assert_eq!(proof.balance, event.reward);
+    assert_eq!(proof.total_hashes, 1);
+    assert_eq!(proof.last_mine_slot, clock.slot);
+    assert_eq!(proof.total_rewards, event.reward);
+    let bus_after = get_state::<Bus>(&mut context, bus_address).await;
+    assert_eq!(bus_after.rewards, bus.rewards - event.reward);
+    let config = get_state::<Config>(&mut context, address).await;
+    assert_eq!(config.total_rewards, event.reward);
+    let ore_proof_after =
+        get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    assert_eq!(ore_proof_after.total_hashes, 1);
+    assert_ne!(ore_proof_after.challenge, ore_proof.challenge);
+
+    // The ORE proof earns the ORE base reward for the same hash.
+    let ore_reward = ore_api::consts::INITIAL_BASE_REWARD_RATE
+        * 2u64.pow(solution.to_hash().difficulty() - ore_api::consts::INITIAL_MIN_DIFFICULTY);
+    assert_eq!(ore_proof_after.balance, ore_reward);
+    assert_eq!(ore_proof_after.total_rewards, ore_reward);
+
+    // The same solution does not solve the new challenge.
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        signer,
+        signer,
+        bus_address,
+        ore_api::consts::BUS_ADDRESSES[0],
+        solution,
+        vec![],
+        None,
+    );
+    assert!(try_process_signed(&mut context, ix, &[&miner]).await.is_err());
+
+    // Claiming the full balance also claims from the ORE proof, after which the proof closes.
+    let beneficiary = token_account_address(signer, MINT_ADDRESS, spl_token::ID);
+    context.set_account(
+        &beneficiary,
+        &token_account(MINT_ADDRESS, signer, 0, spl_token::ID).into(),
+    );
+    let ix = coal_api::sdk::claim(MINT_ADDRESS, signer, beneficiary, event.reward);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+    assert_eq!(token_balance(&mut context, beneficiary).await, event.reward);
+    let treasury_ore_tokens =
+        token_account_address(TREASURY_ADDRESS, ORE_MINT_ADDRESS, spl_token::ID);
+    assert_eq!(token_balance(&mut context, treasury_ore_tokens).await, ore_reward);
+    assert_eq!(
+        token_balance(&mut context, ore_api::consts::TREASURY_TOKENS_ADDRESS).await,
+        ORE_TREASURY_BALANCE - ore_reward
+    );
+    let ore_proof_after =
+        get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    assert_eq!(ore_proof_after.balance, 0);
+    let ix = coal_api::sdk::close(MINT_ADDRESS, signer);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+    for address in [proof_address, ore_proof_address] {
+        let account = context.banks_client.get_account(address).await.unwrap();
+        assert!(account.is_none());
+    }
+    let lamports = context.banks_client.get_balance(signer).await.unwrap();
+    assert!(lamports > 0);
+}
+
+#[tokio::test]
+async fn test_mine_ore_boost() {
+    use steel::Zeroable;
+
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let miner = Keypair::new();
+    let signer = miner.pubkey();
+    let ix = coal_api::sdk::open(MINT_ADDRESS, signer, signer, payer);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+
+    // An ORE boost that triples the base reward, listed in the directory.
+    let boost_address = Pubkey::new_unique();
+    let boost = ore_boost_api::state::Boost {
+        bump: 0,
+        expires_at: i64::MAX,
+        locked: 0,
+        mint: Pubkey::new_unique(),
+        multiplier: 2 * ore_boost_api::consts::BOOST_DENOMINATOR,
+        total_deposits: 0,
+        total_stakers: 0,
+    };
+    context.set_account(&boost_address, &owned_account(boost, ore_boost_api::ID).into());
+    let mut directory = ore_boost_api::state::Directory::zeroed();
+    directory.boosts[0] = boost_address;
+    directory.len = 1;
+    context.set_account(
+        &ore_boost_api::state::directory_pda().0,
+        &owned_account(directory, ore_boost_api::ID).into(),
+    );
+
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.min_difficulty = 1;
+    set_state(&mut context, address, config);
+
+    // The first mine rotates the reservation onto the boost.
+    let proof_address = proof_pda(MINT_ADDRESS, signer).0;
+    let ore_proof_address = ore_api::state::proof_pda(proof_address).0;
+    let reservation_address = ore_boost_api::state::reservation_pda(ore_proof_address).0;
+    let mine = |solution, boost_keys| {
+        coal_api::sdk::mine(
+            MINT_ADDRESS,
+            signer,
+            signer,
+            bus_pda(MINT_ADDRESS, 0).0,
+            ore_api::consts::BUS_ADDRESSES[0],
+            solution,
+            vec![],
+            boost_keys,
+        )
+    };
+    let ore_proof = get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    let ix = mine(solve(ore_proof.challenge, 1), None);
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+    let reservation =
+        get_state::<ore_boost_api::state::Reservation>(&mut context, reservation_address).await;
+    assert_eq!(reservation.boost, boost_address);
+
+    // Mining with the reserved boost pays its multiplier on top of the base reward.
+    let ore_proof = get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    let solution = solve(ore_proof.challenge, 1);
+    let ix = mine(solution, Some((boost_address, reservation_address)));
+    try_process_signed(&mut context, ix, &[&miner]).await.unwrap();
+    let base_reward = ore_api::consts::INITIAL_BASE_REWARD_RATE
+        * 2u64.pow(solution.to_hash().difficulty() - ore_api::consts::INITIAL_MIN_DIFFICULTY);
+    let ore_proof_after =
+        get_state::<ore_api::state::Proof>(&mut context, ore_proof_address).await;
+    assert_eq!(ore_proof_after.balance, ore_proof.balance + 3 * base_reward);
+}
+
+#[tokio::test]
+async fn test_mine_policy() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+
+    // Only the initializer can set the mine policy.
+    let ix = coal_api::sdk::set_mine_policy(
+        payer,
+        MINT_ADDRESS,
+        MINE_POLICY_REQUIRE_AUTH,
+        [Pubkey::default(); MINE_CPI_ALLOWLIST_SIZE],
+    );
+    assert!(try_process(&mut context, ix).await.is_err());
+
+    // A mine without an auth instruction for its proof is rejected.
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.mine_policy = MINE_POLICY_REQUIRE_AUTH;
+    set_state(&mut context, address, config);
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        payer,
+        payer,
+        bus_pda(MINT_ADDRESS, 0).0,
+        ore_api::consts::BUS_ADDRESSES[0],
+        drillx::Solution::new([0; 16], [0; 8]),
+        vec![],
+        None,
+    );
+    assert_mine_err(&mut context, ix.clone(), OreError::MissingAuth).await;
+
+    // A proof that already mined in this slot is rejected, even if that mine was invoked by
+    // another program and so hidden from the transaction's instructions.
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.mine_policy = MINE_POLICY_SINGLE_MINE;
+    set_state(&mut context, address, config);
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let proof_address = proof_pda(MINT_ADDRESS, payer).0;
+    let mut proof = get_state::<Proof>(&mut context, proof_address).await;
+    proof.last_mine_slot = clock.slot;
+    set_state(&mut context, proof_address, proof);
+    assert_mine_err(&mut context, ix, OreError::TooManyMines).await;
+}
+
+#[cfg(feature = "pools")]
+#[tokio::test]
+async fn test_join_pool() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let pool = pool_pda(MINT_ADDRESS, payer).0;
+    set_state(&mut context, pool, pool_state(payer));
+
+    // Joining opens a member account and counts the member.
+    process(&mut context, coal_api::sdk::join_pool(pool, payer)).await;
+    let member = get_state::<PoolMember>(&mut context, pool_member_pda(pool, payer).0).await;
+    assert_eq!(member.authority, payer);
+    assert_eq!(member.pool, pool);
+    assert_eq!(member.total_hashes, 0);
+    assert_eq!(member.id, 0);
+    assert_eq!(member.nonce_range(), 0..POOL_MEMBER_NONCES);
+    let pool = get_state::<Pool>(&mut context, pool).await;
+    assert_eq!(pool.total_members, 1);
+}
+
+#[cfg(feature = "pools")]
+#[tokio::test]
+async fn test_mine_pool_invalid_accounts() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let pool = pool_pda(MINT_ADDRESS, payer).0;
+    let member = pool_member_pda(pool, payer).0;
+    set_state(&mut context, pool, pool_state(payer));
+    set_state(&mut context, member, pool_member_state(pool, payer));
+    let ix = coal_api::sdk::mine_pool(
+        MINT_ADDRESS,
+        payer,
+        payer,
+        bus_pda(MINT_ADDRESS, 0).0,
+        ore_api::consts::BUS_ADDRESSES[0],
+        drillx::Solution::new([0; 16], [0; 8]),
+        vec![],
+        None,
+    );
+
+    // A member of another pool.
+    let other = get_state::<PoolMember>(&mut context, member).await;
+    let address = Pubkey::new_unique();
+    set_state(&mut context, address, PoolMember { pool: Pubkey::new_unique(), ..other });
+    let mut invalid = ix.clone();
+    invalid.accounts[1].pubkey = address;
+    assert_mine_err(&mut context, invalid, OreError::InvalidPoolMember).await;
+
+    // A proof other than the pool's.
+    let mut invalid = ix.clone();
+    invalid.accounts[6].pubkey = proof_pda(MINT_ADDRESS, payer).0;
+    assert_mine_err(&mut context, invalid, OreError::InvalidPool).await;
+}
+
+#[cfg(feature = "pools")]
+#[tokio::test]
+async fn test_submit_pool_invalid_solutions() {
+    use solana_sdk::account::Account;
+    use steel::{Discriminator, Zeroable};
+
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+    let pool = pool_pda(MINT_ADDRESS, payer).0;
+    set_state(&mut context, pool, pool_state(payer));
+    let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+    for (id, member) in members.into_iter().enumerate() {
+        let address = pool_member_pda(pool, member).0;
+        let state = PoolMember { id: id as u64, ..pool_member_state(pool, member) };
+        set_state(&mut context, address, state);
+    }
+
+    // The members mine on the challenge of the pool's ORE proof.
+    let mut ore_proof = ore_api::state::Proof::zeroed();
+    ore_proof.challenge = [7; 32];
+    let ore_proof_address = ore_api::state::proof_pda(proof_pda(MINT_ADDRESS, pool).0).0;
+    let mut data = vec![0; 8 + std::mem::size_of::<ore_api::state::Proof>()];
+    data[0] = ore_api::state::Proof::discriminator();
+    data[8..].copy_from_slice(bytemuck::bytes_of(&ore_proof));
+    context.set_account(
+        &ore_proof_address,
+        &Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: ore_api::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+    let submit = |solutions: &[(Pubkey, drillx::Solution)]| {
+        coal_api::sdk::submit_pool(
+            MINT_ADDRESS,
+            payer,
+            bus_pda(MINT_ADDRESS, 0).0,
+            ore_api::consts::BUS_ADDRESSES[0],
+            solutions,
+            vec![],
+            None,
+        )
+    };
+    let solutions = [
+        drillx::Solution::new([0; 16], 0u64.to_le_bytes()),
+        drillx::Solution::new([0; 16], POOL_MEMBER_NONCES.to_le_bytes()),
+    ];
+
+    // Submissions without members, with a member twice, or with a solution twice, are rejected.
+    assert_mine_err(&mut context, submit(&[]), OreError::InvalidPoolSubmission).await;
+    let ix = submit(&[(members[0], solutions[0]), (members[0], solutions[1])]);
+    assert_mine_err(&mut context, ix, OreError::InvalidPoolSubmission).await;
+    let ix = submit(&[(members[0], solutions[0]), (members[1], solutions[0])]);
+    assert_mine_err(&mut context, ix, OreError::InvalidPoolSubmission).await;
+
+    // Solutions outside of the member's nonce range are rejected.
+    let ix = submit(&[(members[0], solutions[1]), (members[1], solutions[0])]);
+    assert_mine_err(&mut context, ix, OreError::InvalidPoolNonce).await;
+
+    // Solutions that are not valid for the pool's challenge are rejected.
+    let ix = submit(&[(members[0], solutions[0]), (members[1], solutions[1])]);
+    assert_mine_err(&mut context, ix, OreError::HashInvalid).await;
+}
+
+#[cfg(feature = "pools")]
+#[tokio::test]
+async fn test_claim_pool() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    warp_to_next_epoch(&mut context).await;
+    process(&mut context, coal_api::sdk::reset(MINT_ADDRESS, payer)).await;
+
+    // A member with part of the pool proof's balance.
+    let pool = pool_pda(MINT_ADDRESS, payer).0;
+    set_state(&mut context, pool, pool_state(payer));
+    let member = pool_member_pda(pool, payer).0;
+    set_state(
+        &mut context,
+        member,
+        PoolMember {
+            balance: ONE_ORE,
+            ..pool_member_state(pool, payer)
+        },
+    );
+    let proof = proof_pda(MINT_ADDRESS, pool).0;
+    let mut pool_proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+    pool_proof.authority = pool;
+    pool_proof.balance = 3 * ONE_ORE;
+    set_state(&mut context, proof, pool_proof);
+
+    // Claiming debits both the member and the pool proof.
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::claim_pool(MINT_ADDRESS, payer, pool, beneficiary, ONE_ORE / 4);
+    process(&mut context, ix).await;
+    let member_state = get_state::<PoolMember>(&mut context, member).await;
+    assert_eq!(member_state.balance, ONE_ORE - ONE_ORE / 4);
+    assert_eq!(
+        get_state::<Proof>(&mut context, proof).await.balance,
+        3 * ONE_ORE - ONE_ORE / 4
+    );
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + ONE_ORE / 4
+    );
+
+    // Members cannot claim more than their part.
+    let ix = coal_api::sdk::claim_pool(MINT_ADDRESS, payer, pool, beneficiary, ONE_ORE);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::ClaimTooLarge as u32)
+        )
+    );
+}
+
+#[cfg(feature = "pools")]
+#[tokio::test]
+async fn test_claim_pool_other_mint() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let mint = second_mint(&mut context).await;
+
+    // A member with part of the balance of a pool on the default mint.
+    let pool = pool_pda(MINT_ADDRESS, payer).0;
+    set_state(&mut context, pool, pool_state(payer));
+    let member = pool_member_pda(pool, payer).0;
+    set_state(
+        &mut context,
+        member,
+        PoolMember {
+            balance: ONE_ORE,
+            ..pool_member_state(pool, payer)
+        },
+    );
+    let proof = proof_pda(MINT_ADDRESS, pool).0;
+    let mut pool_proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+    pool_proof.authority = pool;
+    set_state(&mut context, proof, pool_proof);
+
+    // The member cannot claim that balance in the tokens of another mint.
+    let beneficiary = token_account_address(payer, mint, spl_token::ID);
+    let mut ix = coal_api::sdk::claim_pool(mint, payer, pool, beneficiary, ONE_ORE);
+    ix.accounts[4].pubkey = proof;
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+    assert_eq!(get_state::<PoolMember>(&mut context, member).await.balance, ONE_ORE);
+}
+
+#[cfg(feature = "boosts")]
+#[tokio::test]
+async fn test_deposit_withdraw() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+
+    // A boost for stakers of the mint, and its token account.
+    let boost = boost_pda(MINT_ADDRESS);
+    set_state(
+        &mut context,
+        boost.0,
+        Boost {
+            mint: MINT_ADDRESS,
+            multiplier: 10_000,
+            expires_at: i64::MAX,
+            total_stake: 0,
+            bump: boost.1 as u64,
+        },
+    );
+    let boost_tokens = token_account_address(boost.0, MINT_ADDRESS, spl_token::ID);
+    context.set_account(
+        &boost_tokens,
+        &token_account(MINT_ADDRESS, boost.0, 0, spl_token::ID).into(),
+    );
+
+    // Deposits move tokens into the boost and track the stake.
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let sender = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    process(&mut context, coal_api::sdk::open_stake(MINT_ADDRESS, payer)).await;
+    let ix = coal_api::sdk::deposit(MINT_ADDRESS, payer, sender, ONE_ORE, &spl_token::ID);
+    process(&mut context, ix).await;
+    let stake = get_state::<Stake>(&mut context, stake_pda(boost.0, payer).0).await;
+    assert_eq!(stake.authority, payer);
+    assert_eq!(stake.boost, boost.0);
+    assert_eq!(stake.balance, ONE_ORE);
+    assert_eq!(stake.last_deposit_at, clock.unix_timestamp);
+    assert_eq!(get_state::<Boost>(&mut context, boost.0).await.total_stake, ONE_ORE);
+    assert_eq!(token_balance(&mut context, boost_tokens).await, ONE_ORE);
+    assert_eq!(token_balance(&mut context, sender).await, INITIAL_BALANCE - ONE_ORE);
+
+    // Withdrawals move them back, up to the stake.
+    let ix = coal_api::sdk::withdraw(MINT_ADDRESS, payer, sender, ONE_ORE / 4, &spl_token::ID);
+    process(&mut context, ix).await;
+    let stake = get_state::<Stake>(&mut context, stake_pda(boost.0, payer).0).await;
+    assert_eq!(stake.balance, ONE_ORE - ONE_ORE / 4);
+    assert_eq!(
+        get_state::<Boost>(&mut context, boost.0).await.total_stake,
+        ONE_ORE - ONE_ORE / 4
+    );
+    assert_eq!(token_balance(&mut context, boost_tokens).await, ONE_ORE - ONE_ORE / 4);
+    let ix = coal_api::sdk::withdraw(MINT_ADDRESS, payer, sender, ONE_ORE, &spl_token::ID);
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::WithdrawTooLarge as u32)
+        )
+    );
+}
+
+/// Opens a second mint with its own config, a funded treasury token account, and an empty token
+/// account for the payer.
+async fn second_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let mint = Pubkey::new_unique();
+    let mut config = get_state::<Config>(context, config_pda(MINT_ADDRESS).0).await;
+    config.mint = mint;
+    set_state(context, config_pda(mint).0, config);
+    context.set_account(
+        &mint,
+        &mint_account(TREASURY_ADDRESS, INITIAL_BALANCE, TOKEN_DECIMALS, spl_token::ID).into(),
+    );
+    context.set_account(
+        &token_account_address(TREASURY_ADDRESS, mint, spl_token::ID),
+        &token_account(mint, TREASURY_ADDRESS, INITIAL_BALANCE, spl_token::ID).into(),
+    );
+    context.set_account(
+        &token_account_address(payer, mint, spl_token::ID),
+        &token_account(mint, payer, 0, spl_token::ID).into(),
+    );
+    mint
+}
+
+#[cfg(feature = "pools")]
+fn pool_member_state(pool: Pubkey, authority: Pubkey) -> PoolMember {
+    PoolMember {
+        authority,
+        pool,
+        total_hashes: 0,
+        total_difficulty: 0,
+        bump: pool_member_pda(pool, authority).1 as u64,
+        balance: 0,
+        total_rewards: 0,
+        id: 0,
+    }
+}
+
+#[cfg(feature = "pools")]
+fn pool_state(authority: Pubkey) -> Pool {
+    let pool = pool_pda(MINT_ADDRESS, authority);
+    Pool {
+        authority,
+        proof: proof_pda(MINT_ADDRESS, pool.0).0,
+        total_members: 0,
+        total_hashes: 0,
+        total_difficulty: 0,
+        bump: pool.1 as u64,
+    }
+}
+
+async fn assert_mine_err(
+    context: &mut ProgramTestContext,
+    ix: Instruction,
+    expected: OreError,
+) {
+    let err = try_process(context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(expected as u32))
+    );
+}
+
+#[tokio::test]
+async fn test_deprecated() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+
+    // The retired stake discriminant fails cleanly instead of panicking.
+    let mut data = vec![5];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    let ix = Instruction {
+        program_id: coal_api::ID,
+        accounts: vec![AccountMeta::new(payer, true)],
+        data,
+    };
+    let err = try_process(&mut context, ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::Deprecated as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_update() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+    let miner = Pubkey::new_unique();
+    process(&mut context, coal_api::sdk::update(MINT_ADDRESS, payer, miner)).await;
+    let proof = get_state::<Proof>(&mut context, proof_pda(MINT_ADDRESS, payer).0).await;
+    assert_eq!(proof.authority, payer);
+    assert_eq!(proof.miner, miner);
+}
+
+#[cfg(feature = "upgrade")]
+#[tokio::test]
+async fn test_upgrade() {
+    let mut context = setup(spl_token::ID).await;
+    let payer = context.payer.pubkey();
+
+    // Open a migration from a legacy mint the payer holds.
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&mut context, address).await;
+    config.migration_mint = MINT_V1_ADDRESS;
+    set_state(&mut context, address, config);
+    let amount = 10u64.pow(TOKEN_DECIMALS_V1 as u32);
+    let sender = token_account_address(payer, MINT_V1_ADDRESS, spl_token::ID);
+    context.set_account(
+        &MINT_V1_ADDRESS,
+        &mint_account(Pubkey::new_unique(), amount, TOKEN_DECIMALS_V1, spl_token::ID).into(),
+    );
+    context.set_account(
+        &sender,
+        &token_account(MINT_V1_ADDRESS, payer, amount, spl_token::ID).into(),
+    );
+
+    // A config at another address is rejected.
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, spl_token::ID);
+    let ix = coal_api::sdk::upgrade(
+        MINT_ADDRESS,
+        MINT_V1_ADDRESS,
+        payer,
+        beneficiary,
+        sender,
+        amount,
+    );
+    let other = Pubkey::new_unique();
+    set_state(&mut context, other, config);
+    let mut other_ix = ix.clone();
+    other_ix.accounts[1].pubkey = other;
+    let err = try_process(&mut context, other_ix).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::InvalidSeeds)
+    );
+
+    // An upgrade that would mint nothing fails without burning the legacy tokens.
+    let mut closed = config;
+    closed.migration_ratio = 0;
+    set_state(&mut context, address, closed);
+    let err = try_process(&mut context, ix.clone()).await.unwrap_err().unwrap();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(OreError::UpgradeTooSmall as u32)
+        )
+    );
+    assert_eq!(token_balance(&mut context, sender).await, amount);
+    set_state(&mut context, address, config);
+
+    // Upgrade burns the legacy tokens and mints at a 1:1 ratio adjusted for decimals.
+    process(&mut context, ix).await;
+    assert_eq!(token_balance(&mut context, sender).await, 0);
+    assert_eq!(mint_supply(&mut context, MINT_V1_ADDRESS).await, 0);
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + ONE_ORE
+    );
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use coal_api::prelude::*;
+use common::*;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+#[tokio::test]
+async fn test_reset_and_claim_spl_token() {
+    test_reset_and_claim(spl_token::ID).await;
+}
+
+#[tokio::test]
+async fn test_reset_and_claim_token_2022() {
+    test_reset_and_claim(spl_token_2022::ID).await;
+}
+
+#[tokio::test]
+async fn test_burn_spl_token() {
+    test_burn(spl_token::ID).await;
+}
+
+#[tokio::test]
+async fn test_burn_token_2022() {
+    test_burn(spl_token_2022::ID).await;
+}
+
+async fn test_reset_and_claim(token_program_id: Pubkey) {
+    let mut context = setup(token_program_id).await;
+    let payer = context.payer.pubkey();
+
+    // Reset funds the treasury under the mint's token program.
+    warp_to_next_epoch(&mut context).await;
+    let ix = coal_api::sdk::reset_with_program_id(MINT_ADDRESS, payer, &token_program_id);
+    process(&mut context, ix).await;
+    let treasury_tokens = token_account_address(TREASURY_ADDRESS, MINT_ADDRESS, token_program_id);
+    let balance = token_balance(&mut context, treasury_tokens).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert!(balance.gt(&0));
+    assert_eq!(config.total_balance, balance);
+
+    // Claim transfers from the treasury to the beneficiary.
+    let beneficiary = token_account_address(payer, MINT_ADDRESS, token_program_id);
+    let ix = coal_api::sdk::claim_with_program_id(
+        MINT_ADDRESS,
+        payer,
+        beneficiary,
+        balance,
+        &token_program_id,
+    );
+    process(&mut context, ix).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(token_balance(&mut context, treasury_tokens).await, 0);
+    assert_eq!(
+        token_balance(&mut context, beneficiary).await,
+        INITIAL_BALANCE + balance
+    );
+    assert_eq!(config.total_balance, 0);
+}
+
+async fn test_burn(token_program_id: Pubkey) {
+    let mut context = setup(token_program_id).await;
+    let payer = context.payer.pubkey();
+    let sender = token_account_address(payer, MINT_ADDRESS, token_program_id);
+    let amount = ONE_ORE * 400;
+    let ix = coal_api::sdk::burn_with_program_id(
+        MINT_ADDRESS,
+        payer,
+        sender,
+        amount,
+        &token_program_id,
+    );
+    process(&mut context, ix).await;
+    let config = get_state::<Config>(&mut context, config_pda(MINT_ADDRESS).0).await;
+    assert_eq!(
+        token_balance(&mut context, sender).await,
+        INITIAL_BALANCE - amount
+    );
+    assert_eq!(
+        mint_supply(&mut context, MINT_ADDRESS).await,
+        INITIAL_BALANCE - amount
+    );
+    assert_eq!(config.total_burned, amount);
+}
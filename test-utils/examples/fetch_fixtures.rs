@@ -0,0 +1,50 @@
+//! Fills the fixtures directory: builds the COAL program at its localnet id, and dumps the ORE and
+//! ORE boost programs and accounts from mainnet. Needs the Solana CLI and `cargo build-sbf`.
+//!
+//! ```text
+//! cargo run -p coal-test-utils --example fetch_fixtures
+//! ```
+
+use std::process::Command;
+
+use coal_test_utils::*;
+
+fn main() {
+    let dir = fixtures_dir();
+    std::fs::create_dir_all(&dir).unwrap();
+
+    // Build COAL with the localnet feature, so it deploys apart from ORE.
+    let root = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+    run(Command::new("cargo").args([
+        "build-sbf",
+        "--manifest-path",
+        &format!("{}/program/Cargo.toml", root),
+        "--features",
+        "localnet",
+    ]));
+    std::fs::copy(
+        format!("{}/target/deploy/ore.so", root),
+        dir.join(COAL_PROGRAM_FIXTURE),
+    )
+    .unwrap();
+
+    // Dump the programs COAL invokes, and the accounts they read.
+    for (program_id, name) in program_fixtures() {
+        if program_id == coal_api::ID {
+            continue;
+        }
+        run(Command::new("solana")
+            .args(["program", "dump", "-um", &program_id.to_string()])
+            .arg(dir.join(name)));
+    }
+    for fixture in account_fixtures() {
+        run(Command::new("solana")
+            .args(["account", "-um", &fixture.address.to_string(), "--output-file"])
+            .arg(dir.join(&fixture.name)));
+    }
+}
+
+fn run(command: &mut Command) {
+    let status = command.status().unwrap();
+    assert!(status.success(), "{:?} failed with {}", command, status);
+}
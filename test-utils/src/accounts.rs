@@ -0,0 +1,194 @@
+use coal_api::prelude::*;
+use litesvm::LiteSVM;
+use solana_program::{program_pack::Pack, rent::Rent};
+use solana_sdk::account::Account;
+use steel::*;
+
+/// Writes the busses, config, treasury and mint of an initialized program into the SVM.
+///
+/// The mint is created at `MINT_ADDRESS` under the given token program with the treasury as its
+/// mint authority, and the treasury token account starts empty.
+pub fn setup_mint(svm: &mut LiteSVM, token_program_id: Pubkey) {
+    // Busses.
+    for id in 0..BUS_COUNT {
+        let bus = Bus {
+            id: id as u64,
+            rewards: 0,
+            theoretical_rewards: 0,
+        };
+        set_state(svm, bus_pda(MINT_ADDRESS, id as u8).0, coal_api::ID, bus);
+    }
+
+    // Config.
+    let mut config = Config::zeroed();
+    config.mint = MINT_ADDRESS;
+    config.max_supply = ONE_ORE * 21_000_000;
+    config.base_reward_rate = INITIAL_BASE_REWARD_RATE;
+    config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
+    config.initial_epoch_rewards = TARGET_EPOCH_REWARDS;
+    config.schedule_epochs = 17_280;
+    config.decay_basis_points = 2000;
+    config.migration_ratio = 10_000;
+    set_state(svm, config_pda(MINT_ADDRESS).0, coal_api::ID, config);
+
+    // Treasury and mint.
+    set_state(svm, TREASURY_ADDRESS, coal_api::ID, Treasury {});
+    set_packed(
+        svm,
+        MINT_ADDRESS,
+        token_program_id,
+        spl_token::state::Mint {
+            mint_authority: Some(TREASURY_ADDRESS).into(),
+            supply: 0,
+            decimals: TOKEN_DECIMALS,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        },
+    );
+    set_token_account(svm, TREASURY_ADDRESS, MINT_ADDRESS, 0, token_program_id);
+}
+
+/// Writes a COAL proof for the authority and the ORE proof it mines through.
+///
+/// Returns the address of the COAL proof.
+pub fn open_proof(svm: &mut LiteSVM, authority: Pubkey) -> Pubkey {
+    let (address, bump) = proof_pda(MINT_ADDRESS, authority);
+    set_state(
+        svm,
+        address,
+        coal_api::ID,
+        Proof {
+            authority,
+            balance: 0,
+            last_stake_at: 0,
+            miner: authority,
+            total_hashes: 0,
+            total_rewards: 0,
+            bump: bump as u64,
+            last_mine_slot: 0,
+        },
+    );
+
+    let mut ore_proof = ore_api::state::Proof::zeroed();
+    ore_proof.authority = address;
+    ore_proof.miner = MINT_ADDRESS;
+    set_state(
+        svm,
+        ore_api::state::proof_pda(address).0,
+        ore_api::ID,
+        ore_proof,
+    );
+    address
+}
+
+/// Writes a token account for the owner at its associated address and returns the address.
+pub fn set_token_account(
+    svm: &mut LiteSVM,
+    owner: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    token_program_id: Pubkey,
+) -> Pubkey {
+    let address = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &owner,
+        &mint,
+        &token_program_id,
+    );
+    set_packed(
+        svm,
+        address,
+        token_program_id,
+        spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: None.into(),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        },
+    );
+    address
+}
+
+/// Reads a program account's state.
+pub fn get_state<T: AccountDeserialize + Discriminator + Pod>(
+    svm: &LiteSVM,
+    address: Pubkey,
+) -> Option<T> {
+    let account = svm.get_account(&address)?;
+    T::try_from_bytes(&account.data).ok().copied()
+}
+
+/// Writes a program account with the given state.
+pub fn set_state<T: Discriminator + Pod>(
+    svm: &mut LiteSVM,
+    address: Pubkey,
+    owner: Pubkey,
+    state: T,
+) {
+    let mut data = vec![0; 8 + std::mem::size_of::<T>()];
+    data[0] = T::discriminator();
+    data[8..].copy_from_slice(bytemuck::bytes_of(&state));
+    set_data(svm, address, owner, data);
+}
+
+/// Writes a token program account with the given packed state.
+///
+/// Classic token state is also valid Token-2022 state for accounts without extensions.
+pub fn set_packed<T: Pack>(svm: &mut LiteSVM, address: Pubkey, owner: Pubkey, state: T) {
+    let mut data = vec![0; T::LEN];
+    state.pack_into_slice(&mut data);
+    set_data(svm, address, owner, data);
+}
+
+fn set_data(svm: &mut LiteSVM, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::prelude::*;
+    use litesvm::LiteSVM;
+    use solana_program::program_pack::Pack;
+    use steel::*;
+
+    use crate::{get_state, open_proof, setup_mint};
+
+    #[test]
+    fn test_setup_mint() {
+        let mut svm = LiteSVM::new();
+        setup_mint(&mut svm, spl_token_2022::ID);
+        let config = get_state::<Config>(&svm, config_pda(MINT_ADDRESS).0).unwrap();
+        assert_eq!(config.mint, MINT_ADDRESS);
+        for id in 0..BUS_COUNT as u8 {
+            let bus = get_state::<Bus>(&svm, bus_pda(MINT_ADDRESS, id).0).unwrap();
+            assert_eq!(bus.id, id as u64);
+        }
+        let mint = svm.get_account(&MINT_ADDRESS).unwrap();
+        assert_eq!(mint.owner, spl_token_2022::ID);
+        let mint = spl_token::state::Mint::unpack(&mint.data).unwrap();
+        assert_eq!(mint.mint_authority, Some(TREASURY_ADDRESS).into());
+    }
+
+    #[test]
+    fn test_open_proof() {
+        let mut svm = LiteSVM::new();
+        let authority = Pubkey::new_unique();
+        let address = open_proof(&mut svm, authority);
+        let proof = get_state::<Proof>(&svm, address).unwrap();
+        assert_eq!(proof.authority, authority);
+        assert_eq!(proof.miner, authority);
+        let ore_proof =
+            get_state::<ore_api::state::Proof>(&svm, ore_api::state::proof_pda(address).0).unwrap();
+        assert_eq!(ore_proof.authority, address);
+    }
+}
@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use litesvm::LiteSVM;
+use solana_program::{pubkey::Pubkey, rent::Rent};
+use solana_sdk::account::Account;
+
+/// Environment variable that overrides the fixtures directory.
+pub const FIXTURES_DIR_ENV: &str = "COAL_FIXTURES_DIR";
+
+/// File name of the COAL program binary, as produced by `cargo build-sbf --features localnet`.
+pub const COAL_PROGRAM_FIXTURE: &str = "coal.so";
+
+/// File name of the ORE program binary, as dumped with `solana program dump`.
+pub const ORE_PROGRAM_FIXTURE: &str = "ore.so";
+
+/// File name of the ORE boost program binary, as dumped with `solana program dump`.
+pub const ORE_BOOST_PROGRAM_FIXTURE: &str = "ore_boost.so";
+
+/// Snapshot of a mainnet account, stored as its raw data in the fixtures directory.
+pub struct AccountFixture {
+    /// The file name of the account data.
+    pub name: String,
+    /// The address the account is written to.
+    pub address: Pubkey,
+    /// The program that owns the account.
+    pub owner: Pubkey,
+}
+
+/// Returns the program fixtures, with the ids they are deployed at.
+///
+/// COAL deploys apart from ORE only under the `localnet` feature of `coal-api`, so tests that
+/// load the fixtures enable it.
+pub fn program_fixtures() -> [(Pubkey, &'static str); 3] {
+    [
+        (coal_api::ID, COAL_PROGRAM_FIXTURE),
+        (ore_api::ID, ORE_PROGRAM_FIXTURE),
+        (ore_boost_api::ID, ORE_BOOST_PROGRAM_FIXTURE),
+    ]
+}
+
+/// Returns the ORE and ORE boost accounts that COAL's CPIs read, as dumped with
+/// `solana account --output-file`.
+pub fn account_fixtures() -> Vec<AccountFixture> {
+    use ore_api::consts::*;
+    let fixture = |name: String, address: Pubkey, owner: Pubkey| AccountFixture {
+        name,
+        address,
+        owner,
+    };
+    let mut fixtures = vec![
+        fixture("ore_config.bin".into(), CONFIG_ADDRESS, ore_api::ID),
+        fixture("ore_mint.bin".into(), MINT_ADDRESS, spl_token::ID),
+        fixture("ore_treasury.bin".into(), TREASURY_ADDRESS, ore_api::ID),
+        fixture("ore_treasury_tokens.bin".into(), TREASURY_TOKENS_ADDRESS, spl_token::ID),
+        fixture(
+            "ore_boost_directory.bin".into(),
+            ore_boost_api::state::directory_pda().0,
+            ore_boost_api::ID,
+        ),
+    ];
+    for (id, address) in BUS_ADDRESSES.iter().enumerate() {
+        fixtures.push(fixture(format!("ore_bus_{}.bin", id), *address, ore_api::ID));
+    }
+    fixtures
+}
+
+/// Returns the directory program fixtures are loaded from.
+///
+/// Defaults to the `fixtures` directory of this crate.
+pub fn fixtures_dir() -> PathBuf {
+    std::env::var_os(FIXTURES_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures"))
+}
+
+/// Returns true if every program and account fixture is present.
+pub fn has_fixtures() -> bool {
+    program_fixtures()
+        .iter()
+        .map(|(_, name)| name.to_string())
+        .chain(account_fixtures().into_iter().map(|a| a.name))
+        .all(|name| fixtures_dir().join(name).exists())
+}
+
+/// Creates a LiteSVM with the COAL, ORE and ORE boost programs, and the ORE and ORE boost
+/// accounts, loaded from the fixtures directory.
+pub fn setup_programs() -> std::io::Result<LiteSVM> {
+    let mut svm = LiteSVM::new();
+    for (program_id, name) in program_fixtures() {
+        load_program(&mut svm, program_id, name)?;
+    }
+    for fixture in account_fixtures() {
+        load_account(&mut svm, &fixture)?;
+    }
+    Ok(svm)
+}
+
+/// Loads a program binary from the fixtures directory.
+pub fn load_program(svm: &mut LiteSVM, program_id: Pubkey, name: &str) -> std::io::Result<()> {
+    let bytes = std::fs::read(fixtures_dir().join(name))?;
+    svm.add_program(program_id, &bytes);
+    Ok(())
+}
+
+/// Loads an account snapshot from the fixtures directory, funded to be rent exempt.
+pub fn load_account(svm: &mut LiteSVM, fixture: &AccountFixture) -> std::io::Result<()> {
+    let data = std::fs::read(fixtures_dir().join(&fixture.name))?;
+    let account = Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: fixture.owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(fixture.address, account)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{:?}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::account_fixtures;
+
+    #[test]
+    fn test_account_fixtures() {
+        let fixtures = account_fixtures();
+        let names: HashSet<_> = fixtures.iter().map(|f| f.name.clone()).collect();
+        let addresses: HashSet<_> = fixtures.iter().map(|f| f.address).collect();
+        assert_eq!(names.len(), fixtures.len());
+        assert_eq!(addresses.len(), fixtures.len());
+        assert!(addresses.contains(&ore_api::consts::CONFIG_ADDRESS));
+        for address in ore_api::consts::BUS_ADDRESSES {
+            assert!(addresses.contains(&address));
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! LiteSVM harness for testing COAL and programs built on top of it.
+//!
+//! Program binaries are loaded from a fixtures directory rather than built on the fly. Account
+//! snapshots are written directly into the SVM, so tests can start from an initialized mint
+//! without running the initialize instruction.
+
+mod accounts;
+mod fixtures;
+mod mining;
+
+pub use accounts::*;
+pub use fixtures::*;
+pub use mining::*;
@@ -0,0 +1,51 @@
+use coal_api::prelude::*;
+use drillx::Solution;
+use litesvm::LiteSVM;
+use steel::Clock;
+
+/// Finds the first solution to the challenge with at least the given difficulty.
+pub fn solve_at_difficulty(challenge: [u8; 32], difficulty: u32) -> Solution {
+    let mut memory = drillx::equix::SolverMemory::new();
+    for nonce in 0u64.. {
+        let nonce = nonce.to_le_bytes();
+        for hash in drillx::hashes_with_memory(&mut memory, &challenge, &nonce) {
+            if hash.difficulty().ge(&difficulty) {
+                return Solution::new(hash.d, nonce);
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Advances the clock by one epoch so the next reset is accepted.
+pub fn warp_epoch(svm: &mut LiteSVM) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = clock.unix_timestamp.saturating_add(EPOCH_DURATION);
+    svm.set_sysvar(&clock);
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::prelude::*;
+    use litesvm::LiteSVM;
+    use steel::Clock;
+
+    use crate::{solve_at_difficulty, warp_epoch};
+
+    #[test]
+    fn test_solve_at_difficulty() {
+        let challenge = [7; 32];
+        let solution = solve_at_difficulty(challenge, 4);
+        assert!(solution.is_valid(&challenge));
+        assert!(solution.to_hash().difficulty().ge(&4));
+    }
+
+    #[test]
+    fn test_warp_epoch() {
+        let mut svm = LiteSVM::new();
+        let before = svm.get_sysvar::<Clock>().unix_timestamp;
+        warp_epoch(&mut svm);
+        let after = svm.get_sysvar::<Clock>().unix_timestamp;
+        assert_eq!(after, before + EPOCH_DURATION);
+    }
+}
@@ -0,0 +1,77 @@
+use coal_api::prelude::*;
+use coal_test_utils::*;
+use litesvm::LiteSVM;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use steel::*;
+
+/// Opens and mines against the real ORE and ORE boost programs.
+///
+/// Ignored by `cargo test`, since the fixtures are fetched rather than checked in. CI fetches them
+/// and runs it with `--include-ignored`, which fails while any fixture is missing.
+#[test]
+#[ignore = "needs the fixtures: cargo run -p coal-test-utils --example fetch_fixtures"]
+fn test_open_and_mine() {
+    assert!(
+        has_fixtures(),
+        "The fixtures are missing from {}, fetch them with cargo run -p coal-test-utils --example fetch_fixtures",
+        fixtures_dir().display()
+    );
+    let mut svm = setup_programs().unwrap();
+    setup_mint(&mut svm, spl_token::ID);
+    let miner = Keypair::new();
+    let signer = miner.pubkey();
+    svm.airdrop(&signer, LAMPORTS_PER_SOL).unwrap();
+
+    // Open creates the ORE proof through the ORE program, and registers it for boosts.
+    send(&mut svm, coal_api::sdk::open(MINT_ADDRESS, signer, signer, signer), &miner);
+    let proof_address = proof_pda(MINT_ADDRESS, signer).0;
+    let ore_proof_address = ore_api::state::proof_pda(proof_address).0;
+    let ore_proof = get_state::<ore_api::state::Proof>(&svm, ore_proof_address).unwrap();
+    assert_eq!(ore_proof.authority, proof_address);
+    assert_eq!(ore_proof.miner, MINT_ADDRESS);
+
+    // Reset funds the busses. The min difficulty of both programs is lowered for a cheap solution.
+    warp_epoch(&mut svm);
+    send(&mut svm, coal_api::sdk::reset(MINT_ADDRESS, signer), &miner);
+    let address = config_pda(MINT_ADDRESS).0;
+    let mut config = get_state::<Config>(&svm, address).unwrap();
+    config.min_difficulty = 1;
+    set_state(&mut svm, address, coal_api::ID, config);
+    let address = ore_api::consts::CONFIG_ADDRESS;
+    let mut ore_config = get_state::<ore_api::state::Config>(&svm, address).unwrap();
+    ore_config.min_difficulty = 1;
+    set_state(&mut svm, address, ore_api::ID, ore_config);
+
+    // Mine a solution to the ORE challenge, which the ORE program verifies.
+    let ix = coal_api::sdk::mine(
+        MINT_ADDRESS,
+        signer,
+        signer,
+        bus_pda(MINT_ADDRESS, 0).0,
+        ore_api::consts::BUS_ADDRESSES[0],
+        solve_at_difficulty(ore_proof.challenge, 1),
+        vec![],
+        None,
+    );
+    send(&mut svm, ix, &miner);
+    let proof = get_state::<Proof>(&svm, proof_address).unwrap();
+    assert_eq!(proof.total_hashes, 1);
+    assert!(proof.balance > 0);
+    let ore_proof_after = get_state::<ore_api::state::Proof>(&svm, ore_proof_address).unwrap();
+    assert_eq!(ore_proof_after.total_hashes, ore_proof.total_hashes + 1);
+    assert_ne!(ore_proof_after.challenge, ore_proof.challenge);
+}
+
+fn send(svm: &mut LiteSVM, ix: Instruction, signer: &Keypair) {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+}